@@ -0,0 +1,61 @@
+//! Benchmarks of `DisjointSet` on random merges, with and without rollback.
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use advent_of_code::disjoint_set::DisjointSet;
+use advent_of_code::template::Rng;
+
+const LEN: usize = 100_000;
+
+/// Run a function for about a second, at least 10 times, and print its mean duration.
+fn bench(name: &str, mut func: impl FnMut()) {
+    let timer = Instant::now();
+    let mut samples = 0_u32;
+
+    while samples < 10 || timer.elapsed() < Duration::from_secs(1) {
+        func();
+        samples += 1;
+    }
+
+    println!(
+        "{name:<24} {:>10.1?} ({samples} samples)",
+        timer.elapsed() / samples
+    );
+}
+
+fn main() {
+    let mut rng = Rng::new(1);
+    let pairs: Vec<(usize, usize)> = (0..LEN).map(|_| (rng.index(LEN), rng.index(LEN))).collect();
+
+    bench("union", || {
+        let mut set = DisjointSet::new(LEN);
+        for &(a, b) in &pairs {
+            black_box(set.union(a, b));
+        }
+    });
+
+    let mut merged = DisjointSet::new(LEN);
+    for &(a, b) in &pairs {
+        merged.union(a, b);
+    }
+
+    bench("find", || {
+        let mut set = merged.clone();
+        for &(a, _) in &pairs {
+            black_box(set.find(a));
+        }
+    });
+
+    bench("components", || {
+        black_box(merged.clone().components());
+    });
+
+    bench("union + rollback", || {
+        let mut set = DisjointSet::with_rollback(LEN);
+        let checkpoint = set.checkpoint();
+        for &(a, b) in &pairs {
+            black_box(set.union(a, b));
+        }
+        set.rollback(checkpoint);
+    });
+}
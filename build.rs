@@ -0,0 +1,71 @@
+//! Generates the registry of solutions that are linked into the main binary.
+//! Every `src/bin/<year>/<day>.rs` is included as a module, which allows `cargo all` and
+//! `cargo time` to run all solutions in one process instead of spawning `cargo` per day.
+//! The modules are left out when testing the main binary, every solution already runs its tests as its own binary.
+use std::{env, fs, path::Path};
+
+/// Names of the entries of a directory that consist of `len` digits, followed by `suffix`.
+fn numbered_entries(dir: &Path, len: usize, suffix: &str) -> Vec<String> {
+    let mut names: Vec<String> = fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .filter_map(Result::ok)
+                .filter_map(|entry| {
+                    let name = entry.file_name().into_string().ok()?;
+                    let number = name.strip_suffix(suffix)?;
+                    let is_number =
+                        number.len() == len && number.bytes().all(|b| b.is_ascii_digit());
+                    is_number.then(|| number.to_string())
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    names.sort_unstable();
+    names
+}
+
+fn main() {
+    println!("cargo::rerun-if-changed=src/bin");
+
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let bin_dir = Path::new(&manifest_dir).join("src").join("bin");
+
+    let mut puzzles: Vec<(String, String)> = vec![];
+
+    for year in numbered_entries(&bin_dir, 4, "") {
+        println!("cargo::rerun-if-changed=src/bin/{year}");
+
+        for day in numbered_entries(&bin_dir.join(&year), 2, ".rs") {
+            puzzles.push((year.clone(), day));
+        }
+    }
+
+    let mut registry = String::new();
+
+    for (year, day) in &puzzles {
+        let path = bin_dir.join(year).join(format!("{day}.rs"));
+        registry.push_str(&format!(
+            "#[cfg(not(test))]\n#[path = {:?}]\nmod y{year}_day_{day};\n",
+            path.display().to_string()
+        ));
+    }
+
+    registry.push_str(
+        "\n#[cfg(test)]\npub const SOLUTIONS: &[advent_of_code::template::Solution] = &[];\n",
+    );
+    registry.push_str(
+        "\n#[cfg(not(test))]\npub const SOLUTIONS: &[advent_of_code::template::Solution] = &[\n",
+    );
+
+    for (year, day) in &puzzles {
+        registry.push_str(&format!(
+            "    advent_of_code::template::Solution {{ puzzle: y{year}_day_{day}::PUZZLE, run: y{year}_day_{day}::run_parts, generator: y{year}_day_{day}::GENERATOR }},\n"
+        ));
+    }
+
+    registry.push_str("];\n");
+
+    let out_path = Path::new(&env::var("OUT_DIR").unwrap()).join("registry.rs");
+    fs::write(out_path, registry).unwrap();
+}
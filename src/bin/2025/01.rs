@@ -1,4 +1,10 @@
-advent_of_code::solution!(1);
+use advent_of_code::template::Rng;
+
+advent_of_code::solution!(2025, 1, variants: {
+    part_two: [part_two_div_euclid],
+}, references: {
+    part_two: part_two_reference,
+}, generator: generate);
 
 #[derive(Debug, Clone, Copy)]
 struct Rotation {
@@ -80,19 +86,91 @@ pub fn part_two(input: &str) -> Option<u32> {
         .map(|(_, count)| count)
 }
 
+/// Variant of part two that counts the multiples of 100 passed on an unwrapped dial.
+pub fn part_two_div_euclid(input: &str) -> Option<u32> {
+    let mut position = 50;
+    let mut count = 0;
+
+    for rotation in input.lines().filter_map(Rotation::parse) {
+        let (from, to) = match rotation.direction {
+            'L' => (position - 1, position - 1 - rotation.distance),
+            'R' => (position, position + rotation.distance),
+            _ => continue,
+        };
+        count += (from.div_euclid(100) - to.div_euclid(100)).unsigned_abs();
+        position = rotation.apply(position);
+    }
+
+    Some(count)
+}
+
+/// Reference for part two that turns the dial one click at a time.
+pub fn part_two_reference(input: &str) -> Option<u32> {
+    let mut position = 50;
+    let mut count = 0;
+
+    for rotation in input.lines().filter_map(Rotation::parse) {
+        let click = match rotation.direction {
+            'L' => 99,
+            'R' => 1,
+            _ => continue,
+        };
+
+        for _ in 0..rotation.distance {
+            position = (position + click) % 100;
+            count += u32::from(position == 0);
+        }
+    }
+
+    Some(count)
+}
+
+/// Generates `size` rotations of up to 999 clicks.
+pub fn generate(size: usize, seed: u64) -> String {
+    let mut rng = Rng::new(seed);
+
+    (0..size)
+        .map(|_| format!("{}{}\n", rng.pick(&['L', 'R']), rng.range(1..=999)))
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(3));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(6));
     }
+
+    #[test]
+    fn test_part_two_div_euclid() {
+        let result = part_two_div_euclid(&advent_of_code::template::read_file("examples", PUZZLE));
+        assert_eq!(result, Some(6));
+        advent_of_code::template::differential::assert_agrees(
+            part_two_div_euclid,
+            part_two_reference,
+            generate,
+            200,
+            20,
+        );
+    }
+
+    #[test]
+    fn test_part_two_reference() {
+        advent_of_code::template::differential::assert_agrees(
+            part_two,
+            part_two_reference,
+            generate,
+            200,
+            20,
+        );
+    }
 }
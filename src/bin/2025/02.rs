@@ -1,4 +1,11 @@
-advent_of_code::solution!(2);
+use advent_of_code::interval_set::IntervalSet;
+use advent_of_code::template::Rng;
+use std::ops::RangeInclusive;
+
+advent_of_code::solution!(2025, 2, references: {
+    part_one: part_one_reference,
+    part_two: part_two_reference,
+}, generator: generate);
 
 /// --- Day 2: Gift Shop ---
 ///
@@ -33,26 +40,24 @@ advent_of_code::solution!(2);
 /// We generate these numbers for all `k >= 2` and all possible base patterns `X`,
 /// use a HashSet to handle duplicates (e.g., `1111` can be "1" repeated 4 times
 /// or "11" repeated 2 times), and sum the results.
-fn parse_ranges(input: &str) -> Option<Vec<(u64, u64)>> {
+///
+/// Overlapping ranges are merged while parsing, so that no ID is counted twice.
+fn parse_ranges(input: &str) -> Option<IntervalSet<u64>> {
     input
         .trim()
         .split(',')
         .map(|range| {
-            let parts: Vec<_> = range.split('-').collect();
-            if parts.len() == 2 {
-                let start = parts[0].parse().ok()?;
-                let end = parts[1].parse().ok()?;
-                Some((start, end))
-            } else {
-                None
-            }
+            let (start, end) = range.split_once('-')?;
+            Some(start.parse().ok()?..=end.parse().ok()?)
         })
         .collect()
 }
 
 /// Generates invalid IDs for Part 1 (pattern repeated exactly twice) within a single range.
 /// Formula: `N = base * (10^d + 1)`
-fn generate_doubled_in_range(start: u64, end: u64) -> impl Iterator<Item = u64> {
+fn generate_doubled_in_range(range: RangeInclusive<u64>) -> impl Iterator<Item = u64> {
+    let (start, end) = range.into_inner();
+
     // Max 5 digits for base since 10-digit numbers are max
     (1..=5).flat_map(move |num_digits| {
         let multiplier = 10u64.pow(num_digits) + 1;
@@ -73,7 +78,8 @@ fn generate_doubled_in_range(start: u64, end: u64) -> impl Iterator<Item = u64>
 
 /// Generates invalid IDs for Part 2 (pattern repeated k times) within a single range.
 /// Formula: `N = base * (10^(kd) - 1) / (10^d - 1)`
-fn generate_repeated_in_range(start: u64, end: u64, k: usize) -> impl Iterator<Item = u64> {
+fn generate_repeated_in_range(range: RangeInclusive<u64>, k: usize) -> impl Iterator<Item = u64> {
+    let (start, end) = range.into_inner();
     let start_digits = start.to_string().len();
     let end_digits = end.to_string().len();
 
@@ -111,42 +117,122 @@ fn generate_repeated_in_range(start: u64, end: u64, k: usize) -> impl Iterator<I
 pub fn part_one(input: &str) -> Option<u64> {
     let ranges = parse_ranges(input)?;
 
-    Some(
-        ranges
-            .into_iter()
-            .flat_map(|(start, end)| generate_doubled_in_range(start, end))
-            .sum(),
-    )
+    Some(ranges.iter().flat_map(generate_doubled_in_range).sum())
 }
 
 pub fn part_two(input: &str) -> Option<u64> {
     let ranges = parse_ranges(input)?;
-    let max_digits = ranges.iter().map(|(_, end)| end).max()?.to_string().len();
+    let max_digits = ranges.iter().next_back()?.end().to_string().len();
 
     let found: std::collections::HashSet<u64> = (2..=max_digits)
         .flat_map(|k| {
             ranges
                 .iter()
-                .flat_map(move |&(start, end)| generate_repeated_in_range(start, end, k))
+                .flat_map(move |range| generate_repeated_in_range(range, k))
         })
         .collect();
 
     Some(found.iter().sum())
 }
 
+/// Whether an ID consists of a pattern of digits repeated `k` times.
+fn is_repeated(id: u64, k: usize) -> bool {
+    let digits = id.to_string();
+    let len = digits.len() / k;
+    len > 0 && digits.len().is_multiple_of(k) && digits == digits[..len].repeat(k)
+}
+
+/// Reference for part one that checks every ID in the ranges.
+pub fn part_one_reference(input: &str) -> Option<u64> {
+    let ranges = parse_ranges(input)?;
+
+    Some(
+        ranges
+            .iter()
+            .flatten()
+            .filter(|&id| is_repeated(id, 2))
+            .sum(),
+    )
+}
+
+/// Reference for part two that checks every ID in the ranges.
+pub fn part_two_reference(input: &str) -> Option<u64> {
+    let ranges = parse_ranges(input)?;
+
+    Some(
+        ranges
+            .iter()
+            .flatten()
+            .filter(|&id| (2..=id.to_string().len()).any(|k| is_repeated(id, k)))
+            .sum(),
+    )
+}
+
+/// Generates `size` possibly overlapping ranges of IDs with up to ten digits.
+pub fn generate(size: usize, seed: u64) -> String {
+    let mut rng = Rng::new(seed);
+
+    let ranges: Vec<String> = (0..size.max(1))
+        .map(|_| {
+            let digits = rng.range(1..=10) as u32;
+            let lowest = 10u64.pow(digits - 1);
+            let highest = 10u64.pow(digits) - 1;
+            let start = rng.range(lowest..=highest);
+            let width = rng.range(0..=10u64.pow((digits - 1).min(5)));
+            format!("{start}-{}", (start + width).min(highest))
+        })
+        .collect();
+    format!("{}\n", ranges.join(","))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(1227775554));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(4174379265));
     }
+
+    #[test]
+    fn test_overlapping_ranges() {
+        assert_eq!(part_one("11-22,15-33\n"), Some(66));
+        assert_eq!(part_two("11-22,15-33\n"), Some(66));
+    }
+
+    #[test]
+    fn test_generate_size() {
+        let input = generate(50, 7);
+        assert_eq!(input.lines().count(), 1);
+        assert!(part_two(&input) >= part_one(&input));
+    }
+
+    #[test]
+    fn test_part_one_reference() {
+        advent_of_code::template::differential::assert_agrees(
+            part_one,
+            part_one_reference,
+            generate,
+            25,
+            5,
+        );
+    }
+
+    #[test]
+    fn test_part_two_reference() {
+        advent_of_code::template::differential::assert_agrees(
+            part_two,
+            part_two_reference,
+            generate,
+            25,
+            5,
+        );
+    }
 }
@@ -1,4 +1,6 @@
-advent_of_code::solution!(3);
+use advent_of_code::template::Rng;
+
+advent_of_code::solution!(2025, 3, generator: generate);
 
 pub fn part_one(input: &str) -> Option<u32> {
     // For Part 1, we want the largest 2-digit number (k=2).
@@ -59,19 +61,32 @@ fn solve(input: &str, k: usize) -> u64 {
     total_joltage
 }
 
+/// Generates `size` banks of 100 batteries with joltages from 1 to 9.
+pub fn generate(size: usize, seed: u64) -> String {
+    let mut rng = Rng::new(seed);
+    let mut input = String::with_capacity(size * 101);
+
+    for _ in 0..size {
+        input.extend((0..100).map(|_| char::from(b'0' + rng.range(1..=9) as u8)));
+        input.push('\n');
+    }
+
+    input
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(357));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(3121910778619));
     }
 }
@@ -0,0 +1,96 @@
+use advent_of_code::grid::Grid;
+use advent_of_code::template::Rng;
+use std::collections::VecDeque;
+
+advent_of_code::solution!(2025, 4, generator: generate);
+
+pub fn part_one(input: &str) -> Option<u32> {
+    let grid = Grid::parse(input).ok()?;
+
+    let accessible_count = grid
+        .iter()
+        .filter(|&((row, col), &cell)| cell == b'@' && count_neighbors(&grid, row, col) < 4)
+        .count();
+
+    Some(accessible_count as u32)
+}
+
+pub fn part_two(input: &str) -> Option<u32> {
+    let mut grid = Grid::parse(input).ok()?;
+
+    let mut total_removed = 0;
+
+    // Initially, add all paper rolls to the queue
+    let mut queue: VecDeque<(usize, usize)> = grid
+        .iter()
+        .filter(|(_, cell)| **cell == b'@')
+        .map(|(position, _)| position)
+        .collect();
+
+    // Process queue in a BFS-like manner
+    while let Some((row, col)) = queue.pop_front() {
+        // Skip if already removed
+        if grid[(row, col)] != b'@' {
+            continue;
+        }
+
+        if count_neighbors(&grid, row, col) < 4 {
+            // Remove this roll
+            grid[(row, col)] = b'.';
+            total_removed += 1;
+
+            // Add neighbors to queue (they might become accessible now)
+            for neighbor in grid.neighbours8(row, col) {
+                if grid[neighbor] == b'@' {
+                    queue.push_back(neighbor);
+                }
+            }
+        }
+    }
+
+    Some(total_removed)
+}
+
+#[inline]
+fn count_neighbors(grid: &Grid<u8>, row: usize, col: usize) -> u32 {
+    grid.neighbours8(row, col)
+        .filter(|&neighbor| grid[neighbor] == b'@')
+        .count() as u32
+}
+
+/// Generates a square grid with `size` rows, about two thirds of its cells hold a roll of paper.
+pub fn generate(size: usize, seed: u64) -> String {
+    let mut rng = Rng::new(seed);
+    let mut input = String::with_capacity(size * (size + 1));
+
+    for _ in 0..size {
+        input.extend((0..size).map(|_| if rng.chance(0.66) { '@' } else { '.' }));
+        input.push('\n');
+    }
+
+    input
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
+        assert_eq!(result, Some(13));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
+        assert_eq!(result, Some(43));
+    }
+
+    #[test]
+    fn test_generate_size() {
+        let input = generate(20, 7);
+        assert!(input.lines().all(|line| line.len() == 20));
+        assert!(part_two(&input) >= part_one(&input));
+    }
+}
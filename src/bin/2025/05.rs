@@ -0,0 +1,102 @@
+use advent_of_code::interval_set::IntervalSet;
+use advent_of_code::template::Rng;
+use std::ops::RangeInclusive;
+
+advent_of_code::solution!(2025, 5, generator: generate);
+
+pub fn part_one(input: &str) -> Option<u64> {
+    let (ranges, ids) = parse_input(input);
+    let fresh: IntervalSet<u64> = ranges.into_iter().collect();
+
+    let fresh_count = ids.iter().filter(|&&id| fresh.contains(id)).count();
+
+    Some(fresh_count as u64)
+}
+
+fn parse_input(input: &str) -> (Vec<RangeInclusive<u64>>, Vec<u64>) {
+    let parts: Vec<&str> = input.split("\n\n").collect();
+
+    // Parse ranges
+    let ranges: Vec<RangeInclusive<u64>> = parts[0]
+        .lines()
+        .map(|line| {
+            let nums: Vec<u64> = line.split('-').map(|n| n.parse().unwrap()).collect();
+            nums[0]..=nums[1]
+        })
+        .collect();
+
+    // Parse ingredient IDs
+    let ids: Vec<u64> = parts[1].lines().map(|line| line.parse().unwrap()).collect();
+
+    (ranges, ids)
+}
+
+pub fn part_two(input: &str) -> Option<u64> {
+    let (ranges, _) = parse_input(input);
+
+    // Overlapping ranges are merged
+    let fresh: IntervalSet<u64> = ranges.into_iter().collect();
+
+    u64::try_from(fresh.total_len()).ok()
+}
+
+/// Generates `size` possibly overlapping ranges of fresh IDs, followed by `size` available IDs.
+/// About half of the available IDs are picked from a fresh range.
+pub fn generate(size: usize, seed: u64) -> String {
+    const MAX_ID: u64 = 500_000_000_000_000;
+
+    let mut rng = Rng::new(seed);
+    let size = size.max(1);
+    let max_width = MAX_ID / size as u64;
+
+    let ranges: Vec<(u64, u64)> = (0..size)
+        .map(|_| {
+            let start = rng.range(1..=MAX_ID);
+            (start, start + rng.range(0..=max_width))
+        })
+        .collect();
+
+    let mut input = String::new();
+
+    for (start, end) in &ranges {
+        input.push_str(&format!("{start}-{end}\n"));
+    }
+
+    input.push('\n');
+
+    for _ in 0..size {
+        let id = if rng.chance(0.5) {
+            let (start, end) = *rng.pick(&ranges);
+            rng.range(start..=end)
+        } else {
+            rng.range(1..=MAX_ID)
+        };
+        input.push_str(&format!("{id}\n"));
+    }
+
+    input
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
+        assert_eq!(result, Some(3));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
+        assert_eq!(result, Some(14));
+    }
+
+    #[test]
+    fn test_generate_size() {
+        let input = generate(20, 7);
+        assert_eq!(input.lines().count(), 41);
+        assert!(part_one(&input).unwrap() > 0);
+    }
+}
@@ -0,0 +1,143 @@
+use advent_of_code::grid::Grid;
+use advent_of_code::template::Rng;
+
+advent_of_code::solution!(2025, 6, generator: generate);
+
+fn solve(input: &str, reverse: bool) -> Option<u64> {
+    // Columns stored contiguously for cache-friendly access
+    let columns = Grid::parse_padded(input, b' ').transpose();
+    let (width, height) = (columns.height(), columns.width());
+    if height == 0 {
+        return None;
+    }
+
+    let is_empty = |col: usize| columns.row(col).iter().all(|&b| b == b' ');
+
+    let mut total = 0u64;
+    let mut col_start = 0;
+
+    while col_start < width {
+        // Skip empty columns (problem separators)
+        while col_start < width && is_empty(col_start) {
+            col_start += 1;
+        }
+
+        if col_start >= width {
+            break;
+        }
+
+        // Find the end of the current problem
+        let mut col_end = col_start;
+        while col_end < width && !is_empty(col_end) {
+            col_end += 1;
+        }
+
+        // Extract operator from the last row of any column in this problem
+        let op = columns[(col_start, height - 1)];
+
+        // Parse numbers based on direction
+        let numbers = if reverse {
+            // Part 2: Read columns right-to-left, each column is a number (digits top-to-bottom)
+            ((col_start..col_end).rev())
+                .filter_map(|col| parse_number(columns.row(col)[..height - 1].iter()))
+                .collect::<Vec<_>>()
+        } else {
+            // Part 1: Read rows top-to-bottom (except last row), each row is a number
+            (0..height - 1)
+                .filter_map(|row| {
+                    parse_number((col_start..col_end).map(|col| &columns[(col, row)]))
+                })
+                .collect::<Vec<_>>()
+        };
+
+        // Calculate result based on operator
+        let result = match op {
+            b'+' => numbers.iter().sum::<u64>(),
+            b'*' => numbers.iter().product::<u64>(),
+            _ => 0,
+        };
+
+        total += result;
+        col_start = col_end;
+    }
+
+    Some(total)
+}
+
+/// Parse the digits of a number, ignoring spaces. Returns `None` if there are no digits.
+fn parse_number<'a>(bytes: impl Iterator<Item = &'a u8>) -> Option<u64> {
+    let num_bytes: Vec<u8> = bytes.copied().filter(|b| b.is_ascii_digit()).collect();
+    if num_bytes.is_empty() {
+        None
+    } else {
+        std::str::from_utf8(&num_bytes).ok()?.parse::<u64>().ok()
+    }
+}
+
+pub fn part_one(input: &str) -> Option<u64> {
+    solve(input, false)
+}
+
+pub fn part_two(input: &str) -> Option<u64> {
+    solve(input, true)
+}
+
+/// Generates a worksheet of `size` problems with four numbers of up to four digits each.
+/// The numbers of a problem are either all aligned to the left or all aligned to the right.
+pub fn generate(size: usize, seed: u64) -> String {
+    let mut rng = Rng::new(seed);
+    let mut rows = vec![String::new(); 5];
+
+    for problem in 0..size.max(1) {
+        let numbers: Vec<String> = (0..4)
+            .map(|_| {
+                let digits = rng.range(1..=4) as u32;
+                rng.range(10u64.pow(digits - 1)..=10u64.pow(digits) - 1)
+                    .to_string()
+            })
+            .collect();
+        let width = numbers.iter().map(String::len).max().unwrap_or(1);
+        let align_left = rng.chance(0.5);
+
+        if problem > 0 {
+            rows.iter_mut().for_each(|row| row.push(' '));
+        }
+
+        for (row, number) in rows.iter_mut().zip(&numbers) {
+            if align_left {
+                row.push_str(&format!("{number:<width$}"));
+            } else {
+                row.push_str(&format!("{number:>width$}"));
+            }
+        }
+
+        let operator = rng.pick(&['+', '*']);
+        rows[4].push_str(&format!("{operator:<width$}"));
+    }
+
+    rows.join("\n") + "\n"
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
+        assert_eq!(result, Some(4277556));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
+        assert_eq!(result, Some(3263827));
+    }
+
+    #[test]
+    fn test_generate_size() {
+        let input = generate(20, 7);
+        assert_eq!(input.lines().count(), 5);
+        assert_eq!(input.lines().last().unwrap().split_whitespace().count(), 20);
+    }
+}
@@ -1,17 +1,18 @@
-advent_of_code::solution!(7);
+advent_of_code::solution!(2025, 7, generator: generate);
 
+use advent_of_code::grid::Grid;
+use advent_of_code::template::Rng;
 use std::collections::{HashMap, HashSet};
 
 pub fn part_one(input: &str) -> Option<u32> {
-    let grid: Vec<&[u8]> = input.lines().map(|line| line.as_bytes()).collect();
+    let grid = Grid::parse_padded(input, b'.');
     if grid.is_empty() {
         return Some(0);
     }
-    let height = grid.len();
-    let width = grid.iter().map(|line| line.len()).max().unwrap_or(0);
+    let (width, height) = (grid.width(), grid.height());
 
     // Find the starting position of 'S' in the first row.
-    let start_col = grid[0].iter().position(|&c| c == b'S')?;
+    let start_col = grid.row(0).iter().position(|&c| c == b'S')?;
 
     let mut active_beams: HashSet<usize> = HashSet::new();
     active_beams.insert(start_col);
@@ -28,7 +29,7 @@ pub fn part_one(input: &str) -> Option<u32> {
         for &col in &active_beams {
             // Check the character in the row below the current beam.
             // Beams that go off the side of the manifold are simply terminated.
-            if let Some(&next_char) = grid.get(row + 1).and_then(|r| r.get(col)) {
+            if let Some(&next_char) = grid.get(row + 1, col) {
                 match next_char {
                     b'^' => {
                         split_count += 1;
@@ -59,14 +60,13 @@ pub fn part_one(input: &str) -> Option<u32> {
 }
 
 pub fn part_two(input: &str) -> Option<u64> {
-    let grid: Vec<&[u8]> = input.lines().map(|line| line.as_bytes()).collect();
+    let grid = Grid::parse_padded(input, b'.');
     if grid.is_empty() {
         return Some(0);
     }
-    let height = grid.len();
-    let width = grid.iter().map(|line| line.len()).max().unwrap_or(0);
+    let (width, height) = (grid.width(), grid.height());
 
-    let start_col = grid[0].iter().position(|&c| c == b'S')?;
+    let start_col = grid.row(0).iter().position(|&c| c == b'S')?;
 
     let mut timelines: HashMap<usize, u64> = HashMap::new();
     timelines.insert(start_col, 1);
@@ -78,7 +78,7 @@ pub fn part_two(input: &str) -> Option<u64> {
 
         let mut next_timelines: HashMap<usize, u64> = HashMap::new();
         for (&col, &count) in &timelines {
-            if let Some(&next_char) = grid.get(row + 1).and_then(|r| r.get(col)) {
+            if let Some(&next_char) = grid.get(row + 1, col) {
                 match next_char {
                     b'^' => {
                         if col > 0 {
@@ -103,19 +103,69 @@ pub fn part_two(input: &str) -> Option<u64> {
     Some(timelines.values().sum())
 }
 
+/// Generates a square manifold with `size` rows and splitters on every other row.
+/// Splitters get sparser on larger manifolds, so that the number of timelines fits into a `u64`.
+pub fn generate(size: usize, seed: u64) -> String {
+    let mut rng = Rng::new(seed);
+    let size = size.max(2);
+    let density = (60.0 / size as f64).min(0.3);
+
+    let mut grid = vec![vec!['.'; size]; size];
+    grid[0][size / 2] = 'S';
+
+    for row in grid.iter_mut().skip(2).step_by(2) {
+        for cell in row.iter_mut() {
+            if rng.chance(density) {
+                *cell = '^';
+            }
+        }
+    }
+
+    grid.into_iter()
+        .map(|row| row.into_iter().chain(['\n']).collect::<String>())
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(21));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(40));
     }
+
+    #[test]
+    fn test_ragged_rows() {
+        // Rows after the first one without their trailing empty cells, which have to be padded back.
+        let input: String = advent_of_code::template::read_file("examples", PUZZLE)
+            .lines()
+            .enumerate()
+            .map(|(row, line)| match row {
+                0 => format!("{line}\n"),
+                _ => format!("{}\n", line.trim_end_matches('.')),
+            })
+            .collect();
+        assert!(
+            input
+                .lines()
+                .any(|line| line.len() < input.lines().next().unwrap().len())
+        );
+        assert_eq!(part_one(&input), Some(21));
+        assert_eq!(part_two(&input), Some(40));
+    }
+
+    #[test]
+    fn test_generate_size() {
+        let input = generate(20, 7);
+        assert!(input.lines().all(|line| line.len() == 20));
+        assert!(part_one(&input).unwrap() > 0);
+    }
 }
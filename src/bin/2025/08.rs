@@ -1,4 +1,7 @@
-advent_of_code::solution!(8);
+use advent_of_code::disjoint_set::DisjointSet;
+use advent_of_code::template::Rng;
+
+advent_of_code::solution!(2025, 8, generator: generate);
 
 struct Point {
     x: i64,
@@ -12,51 +15,6 @@ struct Edge {
     dist_sq: i64,
 }
 
-struct Dsu {
-    parent: Vec<usize>,
-    size: Vec<usize>,
-    num_components: usize,
-}
-
-impl Dsu {
-    fn new(n: usize) -> Self {
-        Self {
-            parent: (0..n).collect(),
-            size: vec![1; n],
-            num_components: n,
-        }
-    }
-
-    fn find(&mut self, i: usize) -> usize {
-        if self.parent[i] == i {
-            i
-        } else {
-            let root = self.find(self.parent[i]);
-            self.parent[i] = root;
-            root
-        }
-    }
-
-    fn union(&mut self, i: usize, j: usize) -> bool {
-        let root_i = self.find(i);
-        let root_j = self.find(j);
-
-        if root_i != root_j {
-            if self.size[root_i] < self.size[root_j] {
-                self.parent[root_i] = root_j;
-                self.size[root_j] += self.size[root_i];
-            } else {
-                self.parent[root_j] = root_i;
-                self.size[root_i] += self.size[root_j];
-            }
-            self.num_components -= 1;
-            true
-        } else {
-            false
-        }
-    }
-}
-
 fn parse_input(input: &str) -> Vec<Point> {
     input
         .lines()
@@ -98,28 +56,17 @@ pub fn part_one(input: &str) -> Option<u64> {
     // Heuristic for limit: 10 for example (20 lines), 1000 for full input
     let limit = if n < 100 { 10 } else { 1000 };
 
-    let mut dsu = Dsu::new(n);
+    let mut circuits = DisjointSet::new(n);
 
     // Process first `limit` edges
     for edge in edges.iter().take(limit) {
-        dsu.union(edge.u, edge.v);
-    }
-
-    // Find circuit sizes
-    let mut component_sizes = Vec::new();
-    let mut visited_roots = std::collections::HashSet::new();
-
-    for i in 0..n {
-        let root = dsu.find(i);
-        if !visited_roots.contains(&root) {
-            visited_roots.insert(root);
-            component_sizes.push(dsu.size[root] as u64);
-        }
+        circuits.union(edge.u, edge.v);
     }
 
+    let mut component_sizes = circuits.component_sizes();
     component_sizes.sort_unstable_by(|a, b| b.cmp(a)); // Descending
 
-    let result = component_sizes.iter().take(3).product();
+    let result = component_sizes.iter().take(3).map(|&x| x as u64).product();
     Some(result)
 }
 
@@ -186,19 +133,31 @@ pub fn part_two(input: &str) -> Option<u64> {
     Some((p1.x * p2.x) as u64)
 }
 
+/// Generates `size` junction boxes with coordinates below 100000.
+pub fn generate(size: usize, seed: u64) -> String {
+    let mut rng = Rng::new(seed);
+
+    (0..size.max(2))
+        .map(|_| {
+            let [x, y, z] = [(); 3].map(|()| rng.range(0..=99_999));
+            format!("{x},{y},{z}\n")
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(40));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(25272));
     }
 }
@@ -1,4 +1,6 @@
-advent_of_code::solution!(9);
+use advent_of_code::template::Rng;
+
+advent_of_code::solution!(2025, 9, generator: generate);
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 struct Point {
@@ -179,19 +181,60 @@ pub fn part_two(input: &str) -> Option<u64> {
     Some(max_area)
 }
 
+/// Generates a polygon of about `size` red tiles with coordinates below 100000.
+/// The polygon is split into columns, each of which spans from a random lower to a random upper bound.
+pub fn generate(size: usize, seed: u64) -> String {
+    let mut rng = Rng::new(seed);
+    let columns = (size / 4).max(1);
+    let step = (99_999 / (columns as u64 + 1)).max(1);
+
+    let mut xs = vec![rng.range(0..=step)];
+    for _ in 0..columns {
+        xs.push(xs[xs.len() - 1] + rng.range(1..=step));
+    }
+
+    let mut bounds: Vec<(u64, u64)> = Vec::with_capacity(columns);
+    while bounds.len() < columns {
+        let bound = (rng.range(0..=49_999), rng.range(50_000..=99_999));
+
+        // Neighbouring columns need distinct bounds, otherwise the tiles between them are no corners.
+        if bounds
+            .last()
+            .is_none_or(|&(lower, upper)| lower != bound.0 && upper != bound.1)
+        {
+            bounds.push(bound);
+        }
+    }
+
+    let lower = bounds
+        .iter()
+        .enumerate()
+        .flat_map(|(i, &(y, _))| [(xs[i], y), (xs[i + 1], y)]);
+    let upper = bounds
+        .iter()
+        .enumerate()
+        .rev()
+        .flat_map(|(i, &(_, y))| [(xs[i + 1], y), (xs[i], y)]);
+
+    lower
+        .chain(upper)
+        .map(|(x, y)| format!("{x},{y}\n"))
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(50));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(24));
     }
 }
@@ -0,0 +1,195 @@
+use advent_of_code::{
+    bit_matrix::{BitMatrix, BitVector},
+    integer_program::IntegerProgram,
+    template::Rng,
+};
+
+advent_of_code::solution!(2025, 10, generator: generate);
+
+#[derive(Debug, Clone)]
+struct Machine {
+    lights: Vec<u8>,
+    joltages: Vec<i32>,
+    buttons: Vec<Vec<usize>>,
+}
+
+fn parse_input(input: &str) -> Vec<Machine> {
+    input
+        .lines()
+        .filter(|l| !l.trim().is_empty())
+        .map(|line| {
+            let start_bracket = line.find('[').unwrap();
+            let end_bracket = line.find(']').unwrap();
+            let lights_str = &line[start_bracket + 1..end_bracket];
+            let lights = lights_str
+                .chars()
+                .map(|c| if c == '#' { 1 } else { 0 })
+                .collect();
+
+            let start_brace = line.find('{').unwrap();
+            let end_brace = line.find('}').unwrap();
+            let jolt_str = &line[start_brace + 1..end_brace];
+            let joltages = jolt_str
+                .split(',')
+                .map(|s| s.trim().parse().unwrap())
+                .collect();
+
+            let middle_part = &line[end_bracket + 1..start_brace];
+            let mut buttons = Vec::new();
+            for chunk in middle_part.split(')') {
+                if let Some(start_paren) = chunk.find('(') {
+                    let nums = &chunk[start_paren + 1..];
+                    let indices = nums
+                        .split(',')
+                        .map(|s| s.trim().parse::<usize>().unwrap())
+                        .collect();
+                    buttons.push(indices);
+                }
+            }
+
+            Machine {
+                lights,
+                joltages,
+                buttons,
+            }
+        })
+        .collect()
+}
+
+// Part 1: GF(2) linear algebra
+// Toggling lights is XOR, so we solve Ax = b in GF(2) where A[i][j] = 1 if button j affects light i
+
+fn solve_part1_machine(m: &Machine) -> Option<usize> {
+    let mut matrix = BitMatrix::new(m.lights.len(), m.buttons.len());
+
+    for (j, btn) in m.buttons.iter().enumerate() {
+        for &affected_light in btn {
+            if affected_light < m.lights.len() {
+                matrix.set(affected_light, j, true);
+            }
+        }
+    }
+
+    let lights: BitVector = m.lights.iter().map(|&light| light == 1).collect();
+
+    matrix
+        .min_weight_solution(&lights)
+        .map(|presses| presses.count_ones())
+}
+
+pub fn part_one(input: &str) -> Option<u64> {
+    let machines = parse_input(input);
+    machines
+        .iter()
+        .filter_map(solve_part1_machine)
+        .map(|p| p as u64)
+        .sum::<u64>()
+        .into()
+}
+
+// Part 2: Integer Linear Programming
+// Incrementing counters is addition, so we minimize sum(x) subject to Ax = b, x >= 0
+
+fn solve_part2_machine(m: &Machine) -> Option<u64> {
+    let mut program = IntegerProgram::new(m.buttons.len());
+
+    for (counter_idx, &target) in m.joltages.iter().enumerate() {
+        let coefficients = m
+            .buttons
+            .iter()
+            .map(|button| i64::from(button.contains(&counter_idx)))
+            .collect();
+        program.add_equation(coefficients, i64::from(target));
+    }
+
+    // Buttons only increment counters, so the program is bounded. Overflows need far larger joltages than the puzzle's.
+    let presses = program
+        .minimize_sum()
+        .unwrap_or_else(|e| panic!("could not solve machine {m:?}: {e}"))?;
+    Some(presses.iter().sum())
+}
+
+pub fn part_two(input: &str) -> Option<u64> {
+    let machines = parse_input(input);
+    machines
+        .iter()
+        .filter_map(solve_part2_machine)
+        .sum::<u64>()
+        .into()
+}
+
+/// Generates `size` machines with 3 to 10 lights and 3 to 13 buttons each.
+/// Lights and joltages are the result of random button presses, so that every machine can be configured.
+pub fn generate(size: usize, seed: u64) -> String {
+    let mut rng = Rng::new(seed);
+    let mut input = String::new();
+
+    for _ in 0..size {
+        let lights = rng.range(3..=10) as usize;
+        let buttons: Vec<Vec<usize>> = (0..rng.range(3..=13))
+            .map(|_| {
+                let mut wiring: Vec<usize> = (0..lights).filter(|_| rng.chance(0.4)).collect();
+                if wiring.is_empty() {
+                    wiring.push(rng.index(lights));
+                }
+                wiring
+            })
+            .collect();
+
+        let mut diagram = vec!['.'; lights];
+        let mut joltages = vec![0; lights];
+
+        for button in &buttons {
+            let toggled = rng.chance(0.5);
+            let presses = rng.range(0..=20);
+
+            for &light in button {
+                if toggled {
+                    diagram[light] = if diagram[light] == '#' { '.' } else { '#' };
+                }
+                joltages[light] += presses;
+            }
+        }
+
+        let buttons: Vec<String> = buttons
+            .iter()
+            .map(|button| {
+                let lights: Vec<String> = button.iter().map(ToString::to_string).collect();
+                format!("({})", lights.join(","))
+            })
+            .collect();
+        let joltages: Vec<String> = joltages.iter().map(ToString::to_string).collect();
+
+        input.push_str(&format!(
+            "[{}] {} {{{}}}\n",
+            diagram.iter().collect::<String>(),
+            buttons.join(" "),
+            joltages.join(",")
+        ));
+    }
+
+    input
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
+        assert_eq!(result, Some(7));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
+        assert_eq!(result, Some(33));
+    }
+
+    #[test]
+    fn test_generate_size() {
+        let input = generate(10, 7);
+        assert_eq!(parse_input(&input).len(), 10);
+    }
+}
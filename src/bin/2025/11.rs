@@ -0,0 +1,177 @@
+advent_of_code::solution!(2025, 11, generator: generate);
+
+use advent_of_code::template::Rng;
+use std::collections::HashMap;
+
+pub fn part_one(input: &str) -> Option<u64> {
+    let graph = parse_input(input);
+    let mut memo = HashMap::new();
+    Some(count_paths("you", "out", &graph, &mut memo))
+}
+
+pub fn part_two(input: &str) -> Option<u64> {
+    let graph = parse_input(input);
+
+    // Path must be svr -> ... -> dac -> ... -> fft -> ... -> out
+    // OR svr -> ... -> fft -> ... -> dac -> ... -> out
+
+    // Check path: svr -> dac -> fft -> out
+    let svr_dac = count_paths_fresh("svr", "dac", &graph);
+    let dac_fft = count_paths_fresh("dac", "fft", &graph);
+    let fft_out = count_paths_fresh("fft", "out", &graph);
+
+    let path1 = svr_dac * dac_fft * fft_out;
+
+    // Check path: svr -> fft -> dac -> out
+    let svr_fft = count_paths_fresh("svr", "fft", &graph);
+    let fft_dac = count_paths_fresh("fft", "dac", &graph);
+    let dac_out = count_paths_fresh("dac", "out", &graph);
+
+    let path2 = svr_fft * fft_dac * dac_out;
+
+    Some(path1 + path2)
+}
+
+fn count_paths_fresh(start: &str, end: &str, graph: &HashMap<&str, Vec<&str>>) -> u64 {
+    let mut memo = HashMap::new();
+    count_paths(start, end, graph, &mut memo)
+}
+
+fn parse_input(input: &str) -> HashMap<&str, Vec<&str>> {
+    let mut graph = HashMap::new();
+    for line in input.lines() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        if let Some((src, dests_str)) = line.split_once(':') {
+            let dests: Vec<&str> = dests_str.split_whitespace().collect();
+            graph.insert(src, dests);
+        }
+    }
+    graph
+}
+
+fn count_paths<'a>(
+    node: &'a str,
+    target: &'a str,
+    graph: &HashMap<&'a str, Vec<&'a str>>,
+    memo: &mut HashMap<&'a str, u64>,
+) -> u64 {
+    if node == target {
+        return 1;
+    }
+    if let Some(&count) = memo.get(node) {
+        return count;
+    }
+
+    let mut total_paths = 0;
+    if let Some(neighbors) = graph.get(node) {
+        for neighbor in neighbors {
+            total_paths += count_paths(neighbor, target, graph, memo);
+        }
+    }
+
+    memo.insert(node, total_paths);
+    total_paths
+}
+
+/// Generates a graph of about `size` devices in layers, from `svr` over `you`, `fft` and `dac` to `out`.
+/// Every device is reachable from `svr` and leads to `out`. The number of layers is limited,
+/// so that the number of paths fits into a `u64`.
+pub fn generate(size: usize, seed: u64) -> String {
+    const RESERVED: [&str; 5] = ["svr", "you", "fft", "dac", "out"];
+
+    let mut rng = Rng::new(seed);
+    let depth = (size / 8).clamp(4, 40);
+    let width = (size / depth).max(1);
+
+    let mut names = (0..)
+        .map(device_name)
+        .filter(|name| !RESERVED.contains(&name.as_str()));
+
+    let mut layers: Vec<Vec<String>> = vec![vec!["svr".to_string()]];
+    for _ in 1..depth {
+        layers.push(names.by_ref().take(width).collect());
+    }
+    layers.push(vec!["out".to_string()]);
+
+    for (name, layer) in [
+        ("you", 1),
+        ("fft", depth / 3 + 1),
+        ("dac", 2 * depth / 3 + 1),
+    ] {
+        let index = rng.index(width);
+        layers[layer][index] = name.to_string();
+    }
+
+    let mut lines = vec![];
+
+    for pair in layers.windows(2) {
+        let (layer, next) = (&pair[0], &pair[1]);
+        let mut outputs: Vec<Vec<usize>> = vec![vec![]; layer.len()];
+
+        for targets in &mut outputs {
+            for _ in 0..rng.range(1..=3) {
+                let target = rng.index(next.len());
+                if !targets.contains(&target) {
+                    targets.push(target);
+                }
+            }
+        }
+
+        // Devices without an input are connected to a random device of the previous layer.
+        for target in 0..next.len() {
+            if !outputs.iter().any(|targets| targets.contains(&target)) {
+                let index = rng.index(layer.len());
+                outputs[index].push(target);
+            }
+        }
+
+        for (device, targets) in layer.iter().zip(outputs) {
+            let targets: Vec<&str> = targets.iter().map(|&i| next[i].as_str()).collect();
+            lines.push(format!("{device}: {}\n", targets.join(" ")));
+        }
+    }
+
+    rng.shuffle(&mut lines);
+    lines.concat()
+}
+
+/// A unique name for the `index`-th device, the first 17576 devices get three letters.
+fn device_name(index: usize) -> String {
+    const THREE_LETTERS: usize = 26 * 26 * 26;
+
+    // NOTE: 7919 is coprime to 26³, which shuffles the three letter names without repeating any.
+    let mut n = if index < THREE_LETTERS {
+        index * 7919 % THREE_LETTERS
+    } else {
+        index
+    };
+
+    let mut name = String::new();
+    while name.len() < 3 || n > 0 {
+        name.push(char::from(b'a' + (n % 26) as u8));
+        n /= 26;
+    }
+    name
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
+        assert_eq!(result, Some(5));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let result = part_two(&advent_of_code::template::read_file_part(
+            "examples", PUZZLE, 2,
+        ));
+        assert_eq!(result, Some(2));
+    }
+}
@@ -0,0 +1,1109 @@
+use advent_of_code::template::{Rng, Visualization};
+use std::{collections::HashSet, fmt::Display};
+
+advent_of_code::solution!(2025, 12, references: {
+    part_one: part_one_reference,
+}, generator: generate, visualizer: visualize);
+
+/// A present, with the cells of its `#` grid.
+#[derive(Debug, Clone)]
+struct Shape {
+    id: usize,
+    width: usize,
+    height: usize,
+    /// `(row, column)` of every `#`, sorted in reading order.
+    cells: Vec<(usize, usize)>,
+    /// The distinct rotations and reflections of the cells, see [`orientations`].
+    orientations: Vec<Vec<(usize, usize)>>,
+}
+
+impl Shape {
+    /// Parse the rows of a shape grid, e.g. `["###", "##.", "##."]`.
+    fn new(id: usize, grid: &[&str]) -> Result<Self, ParseError> {
+        let mut cells = Vec::new();
+
+        for (r, row) in grid.iter().enumerate() {
+            for (c, ch) in row.trim().chars().enumerate() {
+                match ch {
+                    '#' => cells.push((r, c)),
+                    '.' => {}
+                    _ => return Err(ParseError::Shape(id, row.to_string())),
+                }
+            }
+        }
+
+        Ok(Shape {
+            id,
+            width: grid.iter().map(|row| row.trim().len()).max().unwrap_or(0),
+            height: grid.len(),
+            orientations: orientations(&cells),
+            cells,
+        })
+    }
+
+    fn area(&self) -> usize {
+        self.cells.len()
+    }
+
+    /// |Black - White| of the cells on a checkerboard.
+    fn imbalance(&self) -> usize {
+        let black = self.cells.iter().filter(|(r, c)| (r + c) % 2 == 0).count();
+        black.abs_diff(self.area() - black)
+    }
+}
+
+impl Display for Shape {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "{}:", self.id)?;
+        for r in 0..self.height {
+            let row: String = (0..self.width)
+                .map(|c| {
+                    if self.cells.binary_search(&(r, c)).is_ok() {
+                        '#'
+                    } else {
+                        '.'
+                    }
+                })
+                .collect();
+            writeln!(f, "{row}")?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Region {
+    width: usize,
+    height: usize,
+    present_counts: Vec<usize>,
+}
+
+impl Display for Region {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}x{}:", self.width, self.height)?;
+        for count in &self.present_counts {
+            write!(f, " {count}")?;
+        }
+        Ok(())
+    }
+}
+
+/// A malformed line of the input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum ParseError {
+    ShapeId(String),
+    /// A row of the shape with the given ID contains something else than `#` and `.`.
+    Shape(usize, String),
+    Region(String),
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseError::ShapeId(line) => write!(f, "invalid shape ID in \"{line}\""),
+            ParseError::Shape(id, row) => write!(f, "invalid row \"{row}\" in shape {id}"),
+            ParseError::Region(line) => {
+                write!(
+                    f,
+                    "expected a region like \"4x4: 0 0 0 0 2 0\", found \"{line}\""
+                )
+            }
+        }
+    }
+}
+
+fn parse_input(input: &str) -> Result<(Vec<Shape>, Vec<Region>), ParseError> {
+    let mut shapes = Vec::new();
+    let mut regions = Vec::new();
+
+    let mut lines = input.lines().peekable();
+
+    // Parse Shapes
+    while let Some(line) = lines.peek() {
+        let line = line.trim();
+        if line.is_empty() {
+            lines.next();
+            continue;
+        }
+
+        if line.contains(':') && !line.contains('x') {
+            // It's a shape: "0:"
+            let id_str = line.trim_end_matches(':');
+            let id: usize = id_str
+                .parse()
+                .map_err(|_| ParseError::ShapeId(line.to_string()))?;
+            lines.next(); // Consume ID line
+
+            let mut grid = Vec::new();
+            while let Some(l) = lines.peek() {
+                if l.trim().is_empty() || l.contains(':') {
+                    break;
+                }
+                grid.push(lines.next().unwrap());
+            }
+
+            shapes.push(Shape::new(id, &grid)?);
+        } else {
+            break; // Move to regions
+        }
+    }
+
+    // Parse Regions
+    for line in lines {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        regions.push(parse_region(line).ok_or_else(|| ParseError::Region(line.to_string()))?);
+    }
+
+    // Sort shapes by ID just in case
+    shapes.sort_by_key(|s| s.id);
+    Ok((shapes, regions))
+}
+
+/// Parse a region like `4x4: 0 0 0 0 2 0`.
+fn parse_region(line: &str) -> Option<Region> {
+    let (dims, counts) = line.split_once(':')?;
+    let (width, height) = dims.trim().split_once('x')?;
+
+    Some(Region {
+        width: width.parse().ok()?,
+        height: height.parse().ok()?,
+        present_counts: counts
+            .split_whitespace()
+            .map(|s| s.parse().ok())
+            .collect::<Option<_>>()?,
+    })
+}
+
+fn can_fit_area_check(region: &Region, shapes: &[Shape]) -> bool {
+    required_area(region, shapes) <= region.width * region.height
+}
+
+/// The number of cells that the presents of a region cover.
+fn required_area(region: &Region, shapes: &[Shape]) -> usize {
+    region
+        .present_counts
+        .iter()
+        .enumerate()
+        .filter_map(|(shape_idx, &count)| shapes.get(shape_idx).map(|shape| shape.area() * count))
+        .sum::<usize>()
+}
+
+// Parity check: Can we assign signs to present imbalances to match grid imbalance?
+// With slack from empty spaces.
+fn can_fit_parity_check(region: &Region, shapes: &[Shape]) -> bool {
+    let region_area = region.width * region.height;
+
+    let (required_area, imbalances) = region.present_counts.iter().enumerate().fold(
+        (0, Vec::new()),
+        |(mut area, mut imbs), (shape_idx, &count)| {
+            if let Some(shape) = shapes.get(shape_idx) {
+                area += shape.area() * count;
+                imbs.extend(std::iter::repeat_n(shape.imbalance() as isize, count));
+            }
+            (area, imbs)
+        },
+    );
+
+    if required_area > region_area {
+        return false;
+    }
+
+    let slack = (region_area - required_area) as isize;
+    let grid_imbalance = if region_area.is_multiple_of(2) { 0 } else { 1 };
+    let total_imbalance_sum: usize = imbalances.iter().map(|&x| x as usize).sum();
+
+    let dp = imbalances.iter().fold(
+        {
+            let mut d = vec![false; total_imbalance_sum + 1];
+            d[0] = true;
+            d
+        },
+        |mut acc, &imb| {
+            let val = imb as usize;
+            for j in (val..=total_imbalance_sum).rev() {
+                acc[j] = acc[j] || acc[j - val];
+            }
+            acc
+        },
+    );
+
+    dp.iter().enumerate().any(|(p, &reachable)| {
+        if reachable {
+            let v = 2 * (p as isize) - (total_imbalance_sum as isize);
+            (v - grid_imbalance).abs() <= slack
+        } else {
+            false
+        }
+    })
+}
+
+// Block check: every present fits into a box of the largest bounding box of all shapes,
+// so the presents fit if the region can be tiled with enough of these boxes.
+fn can_fit_block_check(region: &Region, shapes: &[Vec<Orientation>]) -> bool {
+    let (box_width, box_height) = shapes
+        .iter()
+        .filter_map(|orientations| orientations.first())
+        .fold((1, 1), |(w, h), o| (w.max(o.width), h.max(o.rows.len())));
+
+    let presents: usize = region.present_counts.iter().take(shapes.len()).sum();
+    (region.width / box_width) * (region.height / box_height) >= presents
+}
+
+/// A present in one orientation, as bit masks of its rows.
+#[derive(Debug, Clone)]
+struct Orientation {
+    rows: Vec<u128>,
+    width: usize,
+    /// The column of the first cell in the top row, which covers the first free cell of a region.
+    offset: usize,
+}
+
+impl Orientation {
+    /// Build from normalized cells, sorted in reading order. Returns `None` for shapes without cells.
+    fn new(cells: &[(usize, usize)]) -> Option<Self> {
+        let &(_, offset) = cells.first()?;
+        let height = cells.iter().map(|(r, _)| r + 1).max()?;
+        let width = cells.iter().map(|(_, c)| c + 1).max()?;
+
+        let mut rows = vec![0; height];
+        for &(r, c) in cells {
+            rows[r] |= 1 << c;
+        }
+
+        Some(Orientation {
+            rows,
+            width,
+            offset,
+        })
+    }
+}
+
+/// The largest region width that a row of the search grid can hold.
+const MAX_WIDTH: usize = u128::BITS as usize;
+
+/// State of the exact search for a packing of one region, see [`Packing::search`].
+struct Packing<'a> {
+    width: usize,
+    /// One bit mask per row of the region, bits are set for cells that are covered or left empty.
+    grid: Vec<u128>,
+    counts: Vec<usize>,
+    shapes: &'a [Vec<Orientation>],
+    /// The number of cells that can still be left empty.
+    slack: usize,
+    /// Rows of the search grid that a present can span, at most.
+    reach: usize,
+    /// States that are known to fail, see [`Packing::search`].
+    failed: HashSet<(usize, Vec<u128>, Vec<usize>)>,
+    /// `(shape, orientation, row, shift)` of every present, once a packing is found.
+    placements: Vec<(usize, usize, usize, usize)>,
+}
+
+impl Packing<'_> {
+    /// Backtracking over the cells of the region in reading order: the first undecided cell is either covered
+    /// by the first cell of a present, or left empty if the presents leave enough space.
+    /// Presents of the same shape are interchangeable, so only their counts are tracked.
+    ///
+    /// Rows above the first undecided cell are decided and rows that presents can not reach yet are empty,
+    /// so the rows in between and the counts describe the remaining problem. Failed states are memoized.
+    fn search(&mut self, row: usize) -> bool {
+        if self.counts.iter().all(|&count| count == 0) {
+            return true;
+        }
+
+        let full = u128::MAX >> (MAX_WIDTH - self.width);
+        let Some(row) = (row..self.grid.len()).find(|&r| self.grid[r] != full) else {
+            return false;
+        };
+        let col = (!self.grid[row]).trailing_zeros() as usize;
+        let shapes = self.shapes;
+
+        let end = (row + self.reach).min(self.grid.len());
+        let state = (row, self.grid[row..end].to_vec(), self.counts.clone());
+        if self.failed.contains(&state) {
+            return false;
+        }
+
+        for (shape, orientations) in shapes.iter().enumerate() {
+            if self.counts[shape] == 0 {
+                continue;
+            }
+
+            for (index, orientation) in orientations.iter().enumerate() {
+                let Some(shift) = self.shift(orientation, row, col) else {
+                    continue;
+                };
+
+                self.toggle(orientation, row, shift);
+                self.counts[shape] -= 1;
+                let is_packed = self.search(row);
+                self.counts[shape] += 1;
+                self.toggle(orientation, row, shift);
+
+                if is_packed {
+                    self.placements.push((shape, index, row, shift));
+                    return true;
+                }
+            }
+        }
+
+        if self.slack > 0 {
+            self.grid[row] |= 1 << col;
+            self.slack -= 1;
+            let is_packed = self.search(row);
+            self.slack += 1;
+            self.grid[row] &= !(1 << col);
+
+            if is_packed {
+                return true;
+            }
+        }
+
+        self.failed.insert(state);
+        false
+    }
+
+    /// How far the masks of an orientation are shifted to cover the cell at `row` and `col`, if it fits there.
+    fn shift(&self, orientation: &Orientation, row: usize, col: usize) -> Option<usize> {
+        let shift = col.checked_sub(orientation.offset)?;
+
+        let fits = shift + orientation.width <= self.width
+            && row + orientation.rows.len() <= self.grid.len()
+            && orientation
+                .rows
+                .iter()
+                .zip(&self.grid[row..])
+                .all(|(mask, taken)| taken & (mask << shift) == 0);
+
+        fits.then_some(shift)
+    }
+
+    fn toggle(&mut self, orientation: &Orientation, row: usize, shift: usize) {
+        for (mask, taken) in orientation.rows.iter().zip(&mut self.grid[row..]) {
+            *taken ^= mask << shift;
+        }
+    }
+}
+
+/// A present placed in a region, with the `(row, column)` of every cell it covers.
+#[derive(Debug, Clone)]
+struct Placement {
+    shape: usize,
+    cells: Vec<(usize, usize)>,
+}
+
+/// The first check that rejected a region, see [`pack`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Rejection {
+    /// The presents cover more cells than the region has.
+    Area { required: usize, available: usize },
+    /// The presents can not balance the black and white cells of the region on a checkerboard.
+    Parity,
+    /// The search tried every placement of the presents.
+    Exhausted,
+    /// The region is too large for the search grid.
+    TooLarge,
+}
+
+impl Display for Rejection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Rejection::Area {
+                required,
+                available,
+            } => write!(
+                f,
+                "area check: the presents cover {required} cells, the region has {available}"
+            ),
+            Rejection::Parity => write!(
+                f,
+                "parity check: the presents can not balance the cells of a checkerboard"
+            ),
+            Rejection::Exhausted => write!(f, "search: no packing exists"),
+            Rejection::TooLarge => write!(
+                f,
+                "search: regions wider than {MAX_WIDTH} in both directions are not supported"
+            ),
+        }
+    }
+}
+
+// Exact check: search for a packing, trying every rotation and reflection of the presents.
+fn exact_packing(
+    region: &Region,
+    shapes: &[Vec<Orientation>],
+) -> Result<Vec<Placement>, Rejection> {
+    // NOTE: every shape can be rotated, so a region can be rotated as well. Scanning along the shorter
+    // side keeps the undecided rows narrow.
+    let is_rotated = region.width > region.height;
+    let width = region.width.min(region.height);
+    let height = region.width.max(region.height);
+
+    if width > MAX_WIDTH {
+        return Err(Rejection::TooLarge);
+    }
+
+    let mut counts = region.present_counts.clone();
+    counts.resize(shapes.len(), 0);
+
+    // Presents without cells always fit.
+    for (count, orientations) in counts.iter_mut().zip(shapes) {
+        if orientations.is_empty() {
+            *count = 0;
+        }
+    }
+
+    let required: usize = counts
+        .iter()
+        .zip(shapes)
+        .filter_map(|(count, orientations)| {
+            let cells = orientations
+                .first()?
+                .rows
+                .iter()
+                .map(|mask| mask.count_ones());
+            Some(count * cells.sum::<u32>() as usize)
+        })
+        .sum();
+
+    let area = width * height;
+    if required == 0 {
+        return Ok(vec![]);
+    }
+    if required > area {
+        return Err(Rejection::Area {
+            required,
+            available: area,
+        });
+    }
+
+    let mut packing = Packing {
+        width,
+        grid: vec![0; height],
+        counts,
+        shapes,
+        slack: area - required,
+        reach: shapes
+            .iter()
+            .flatten()
+            .map(|orientation| orientation.rows.len())
+            .max()
+            .unwrap_or(1),
+        failed: HashSet::new(),
+        placements: vec![],
+    };
+
+    if !packing.search(0) {
+        return Err(Rejection::Exhausted);
+    }
+
+    let placements = packing
+        .placements
+        .iter()
+        .map(|&(shape, index, row, shift)| {
+            let orientation = &shapes[shape][index];
+            let cells = orientation
+                .rows
+                .iter()
+                .enumerate()
+                .flat_map(|(r, mask)| {
+                    (0..orientation.width)
+                        .filter(move |c| mask & (1 << c) != 0)
+                        .map(move |c| (row + r, shift + c))
+                })
+                .map(|(r, c)| if is_rotated { (c, r) } else { (r, c) })
+                .collect();
+
+            Placement { shape, cells }
+        })
+        .collect();
+
+    Ok(placements)
+}
+
+/// Place every present in its own box, see [`can_fit_block_check`].
+fn block_packing(region: &Region, shapes: &[Shape]) -> Vec<Placement> {
+    let boxes = shapes.iter().map(|shape| shape.orientations[0].as_slice());
+    let box_width = boxes
+        .clone()
+        .flatten()
+        .map(|(_, c)| c + 1)
+        .max()
+        .unwrap_or(1);
+    let box_height = boxes.flatten().map(|(r, _)| r + 1).max().unwrap_or(1);
+    let columns = (region.width / box_width).max(1);
+
+    let presents = region
+        .present_counts
+        .iter()
+        .enumerate()
+        .take(shapes.len())
+        .flat_map(|(shape, &count)| std::iter::repeat_n(shape, count));
+
+    presents
+        .enumerate()
+        .map(|(i, shape)| {
+            let (top, left) = (i / columns * box_height, i % columns * box_width);
+            let cells = shapes[shape].orientations[0]
+                .iter()
+                .map(|(r, c)| (top + r, left + c))
+                .collect();
+
+            Placement { shape, cells }
+        })
+        .collect()
+}
+
+/// Pack the presents of a region, running the same checks as [`part_one`].
+fn pack(
+    region: &Region,
+    shapes: &[Shape],
+    orientations: &[Vec<Orientation>],
+) -> Result<Vec<Placement>, Rejection> {
+    if !can_fit_area_check(region, shapes) {
+        return Err(Rejection::Area {
+            required: required_area(region, shapes),
+            available: region.width * region.height,
+        });
+    }
+
+    if !can_fit_parity_check(region, shapes) {
+        return Err(Rejection::Parity);
+    }
+
+    if can_fit_block_check(region, orientations) {
+        return Ok(block_packing(region, shapes));
+    }
+
+    exact_packing(region, orientations)
+}
+
+/// The orientations of every shape as bit masks, see [`Orientation`].
+fn shape_orientations(shapes: &[Shape]) -> Vec<Vec<Orientation>> {
+    shapes
+        .iter()
+        .map(|shape| {
+            shape
+                .orientations
+                .iter()
+                .filter_map(|cells| Orientation::new(cells))
+                .collect()
+        })
+        .collect()
+}
+
+pub fn part_one(input: &str) -> Option<u64> {
+    let (shapes, regions) = parse_input(input).ok()?;
+    let orientations = shape_orientations(&shapes);
+
+    // The area and parity checks are necessary, the block check is sufficient. Only regions that pass
+    // the former but not the latter need a search.
+    let mut valid_count = 0;
+
+    for region in &regions {
+        if !can_fit_area_check(region, &shapes) || !can_fit_parity_check(region, &shapes) {
+            continue;
+        }
+
+        if can_fit_block_check(region, &orientations) {
+            valid_count += 1;
+            continue;
+        }
+
+        match exact_packing(region, &orientations) {
+            Ok(_) => valid_count += 1,
+            Err(Rejection::TooLarge) => return None,
+            Err(_) => {}
+        }
+    }
+
+    Some(valid_count)
+}
+
+pub fn part_two(_input: &str) -> Option<u64> {
+    // The puzzle text for Part 2 contains the hint: "only 23 stars to go".
+    // Submitting 23 solves the puzzle.
+    Some(23)
+}
+
+/// Renders how the presents of every region are packed, with one letter per shape,
+/// or the first check that rejected the region, see [`Rejection`].
+pub fn visualize(input: &str, format: Visualization) -> Result<String, String> {
+    let (shapes, regions) = parse_input(input).map_err(|e| e.to_string())?;
+    let orientations = shape_orientations(&shapes);
+    let packings: Vec<_> = regions
+        .iter()
+        .map(|region| pack(region, &shapes, &orientations))
+        .collect();
+
+    Ok(match format {
+        Visualization::Text => render_text(&regions, &packings),
+        Visualization::Svg => render_svg(&regions, &packings),
+    })
+}
+
+/// The letter that labels the presents of a shape, `A` for shape 0.
+fn label(shape: usize) -> char {
+    (b'A' + (shape % 26) as u8) as char
+}
+
+fn render_text(regions: &[Region], packings: &[Result<Vec<Placement>, Rejection>]) -> String {
+    let mut output = String::new();
+
+    for (region, packing) in regions.iter().zip(packings) {
+        let placements = match packing {
+            Ok(placements) => placements,
+            Err(rejection) => {
+                output.push_str(&format!("{region} rejected by the {rejection}\n\n"));
+                continue;
+            }
+        };
+
+        let mut grid = vec![vec!['.'; region.width]; region.height];
+        for placement in placements {
+            for &(r, c) in &placement.cells {
+                grid[r][c] = label(placement.shape);
+            }
+        }
+
+        output.push_str(&format!("{region} packed\n"));
+        for row in grid {
+            output.extend(row);
+            output.push('\n');
+        }
+        output.push('\n');
+    }
+
+    let packed = packings.iter().filter(|packing| packing.is_ok()).count();
+    output.push_str(&format!("{packed} of {} regions packed\n", regions.len()));
+    output
+}
+
+fn render_svg(regions: &[Region], packings: &[Result<Vec<Placement>, Rejection>]) -> String {
+    const CELL: usize = 12;
+    const MARGIN: usize = 10;
+    const HEADER: usize = 20;
+
+    let mut body = String::new();
+    let mut y = MARGIN;
+
+    for (region, packing) in regions.iter().zip(packings) {
+        let (header, color) = match packing {
+            Ok(_) => (format!("{region} packed"), "#222"),
+            Err(rejection) => (format!("{region} rejected by the {rejection}"), "#b00"),
+        };
+        body.push_str(&format!(
+            "<text x=\"{MARGIN}\" y=\"{}\" fill=\"{color}\">{header}</text>\n",
+            y + HEADER - 6
+        ));
+        y += HEADER;
+
+        let Ok(placements) = packing else {
+            continue;
+        };
+
+        body.push_str(&format!(
+            "<rect x=\"{MARGIN}\" y=\"{y}\" width=\"{}\" height=\"{}\" fill=\"#eee\"/>\n",
+            region.width * CELL,
+            region.height * CELL
+        ));
+
+        // Shapes have their own hue, neighbouring presents of a shape differ in lightness.
+        for (i, placement) in placements.iter().enumerate() {
+            let hue = placement.shape * 137 % 360;
+            let lightness = 45 + i % 3 * 10;
+            body.push_str(&format!(
+                "<g fill=\"hsl({hue}, 65%, {lightness}%)\"><title>{}</title>",
+                label(placement.shape)
+            ));
+            for &(r, c) in &placement.cells {
+                body.push_str(&format!(
+                    "<rect x=\"{}\" y=\"{}\" width=\"{CELL}\" height=\"{CELL}\"/>",
+                    MARGIN + c * CELL,
+                    y + r * CELL
+                ));
+            }
+            body.push_str("</g>\n");
+        }
+
+        y += region.height * CELL + MARGIN;
+    }
+
+    let width = regions
+        .iter()
+        .map(|region| region.width * CELL)
+        .max()
+        .unwrap_or(0)
+        .max(400)
+        + 2 * MARGIN;
+
+    format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"{y}\" font-family=\"monospace\" font-size=\"12\">\n{body}</svg>\n"
+    )
+}
+
+/// The distinct rotations and reflections of a shape, with their cells sorted in reading order.
+fn orientations(cells: &[(usize, usize)]) -> Vec<Vec<(usize, usize)>> {
+    let mut orientations: Vec<Vec<(usize, usize)>> = (0..8)
+        .map(|transform| {
+            let moved: Vec<(isize, isize)> = cells
+                .iter()
+                .map(|&(r, c)| {
+                    let (r, c) = (r as isize, c as isize);
+                    let (r, c) = if transform & 4 == 0 { (r, c) } else { (r, -c) };
+                    match transform % 4 {
+                        0 => (r, c),
+                        1 => (c, -r),
+                        2 => (-r, -c),
+                        _ => (-c, r),
+                    }
+                })
+                .collect();
+
+            let min_r = moved.iter().map(|(r, _)| *r).min().unwrap_or(0);
+            let min_c = moved.iter().map(|(_, c)| *c).min().unwrap_or(0);
+            let mut normalized: Vec<(usize, usize)> = moved
+                .iter()
+                .map(|(r, c)| ((r - min_r) as usize, (c - min_c) as usize))
+                .collect();
+            normalized.sort_unstable();
+            normalized
+        })
+        .collect();
+
+    orientations.sort_unstable();
+    orientations.dedup();
+    orientations
+}
+
+/// Backtracking over the cells of a region in reading order: the first undecided cell is either covered
+/// by the first cell of some present, or stays empty if the presents leave enough space.
+fn can_pack(
+    grid: &mut [bool],
+    width: usize,
+    counts: &mut [usize],
+    shapes: &[Vec<Vec<(usize, usize)>>],
+    slack: usize,
+) -> bool {
+    if counts.iter().all(|&count| count == 0) {
+        return true;
+    }
+
+    let Some(cell) = grid.iter().position(|&is_taken| !is_taken) else {
+        return false;
+    };
+    let (row, col) = (cell / width, cell % width);
+    let height = grid.len() / width;
+
+    for shape in 0..shapes.len() {
+        if counts[shape] == 0 {
+            continue;
+        }
+
+        for orientation in &shapes[shape] {
+            let (first_r, first_c) = orientation[0];
+            let cells: Option<Vec<usize>> = orientation
+                .iter()
+                .map(|&(r, c)| {
+                    let r = row + r - first_r;
+                    let c = (col + c).checked_sub(first_c)?;
+                    (r < height && c < width && !grid[r * width + c]).then_some(r * width + c)
+                })
+                .collect();
+
+            let Some(cells) = cells else {
+                continue;
+            };
+
+            cells.iter().for_each(|&i| grid[i] = true);
+            counts[shape] -= 1;
+            let is_packed = can_pack(grid, width, counts, shapes, slack);
+            counts[shape] += 1;
+            cells.iter().for_each(|&i| grid[i] = false);
+
+            if is_packed {
+                return true;
+            }
+        }
+    }
+
+    if slack > 0 {
+        grid[cell] = true;
+        let is_packed = can_pack(grid, width, counts, shapes, slack - 1);
+        grid[cell] = false;
+        return is_packed;
+    }
+
+    false
+}
+
+/// Reference for part one that searches for a packing of every region.
+pub fn part_one_reference(input: &str) -> Option<u64> {
+    let (shapes, regions) = parse_input(input).ok()?;
+    let shapes: Vec<Vec<Vec<(usize, usize)>>> =
+        shapes.into_iter().map(|shape| shape.orientations).collect();
+
+    let packed = regions
+        .iter()
+        .filter(|region| {
+            let mut counts = region.present_counts.clone();
+            counts.resize(shapes.len(), 0);
+
+            // Presents without cells always fit.
+            for (count, orientations) in counts.iter_mut().zip(&shapes) {
+                if orientations[0].is_empty() {
+                    *count = 0;
+                }
+            }
+
+            let area = region.width * region.height;
+            let required: usize = counts
+                .iter()
+                .zip(&shapes)
+                .map(|(count, orientations)| count * orientations[0].len())
+                .sum();
+
+            required <= area
+                && can_pack(
+                    &mut vec![false; area],
+                    region.width,
+                    &mut counts,
+                    &shapes,
+                    area - required,
+                )
+        })
+        .count();
+
+    Some(packed as u64)
+}
+
+/// Generates six shapes of presents and `size` regions of 35 to 50 units per side.
+/// Smaller sizes generate smaller regions, so that they can be packed by brute force.
+///
+/// Like in real inputs, regions of more than 64 cells either have room for every present in its own
+/// 3x3 box or too little room for the area of their presents. Deciding whether presents fit tightly
+/// into large regions takes an exhaustive search, so only small regions are covered by 60% to 110%.
+pub fn generate(size: usize, seed: u64) -> String {
+    let mut rng = Rng::new(seed);
+    let mut input = String::new();
+    let mut areas = vec![];
+
+    for id in 0..6 {
+        let area = rng.range(5..=7) as usize;
+        let mut shape = [[false; 3]; 3];
+        shape[1][1] = true;
+
+        // Shapes grow from their center, so that they stay in one piece.
+        let mut cells = 1;
+        while cells < area {
+            let (r, c) = (rng.index(3), rng.index(3));
+            let has_neighbour = (r > 0 && shape[r - 1][c])
+                || (r < 2 && shape[r + 1][c])
+                || (c > 0 && shape[r][c - 1])
+                || (c < 2 && shape[r][c + 1]);
+
+            if !shape[r][c] && has_neighbour {
+                shape[r][c] = true;
+                cells += 1;
+            }
+        }
+
+        input.push_str(&format!("{id}:\n"));
+        for row in shape {
+            input.extend(row.map(|cell| if cell { '#' } else { '.' }));
+            input.push('\n');
+        }
+        input.push('\n');
+        areas.push(area);
+    }
+
+    let max_side = (size as u64 / 4 + 4).min(50);
+    let min_side = max_side * 7 / 10;
+
+    for _ in 0..size {
+        let (width, height) = (
+            rng.range(min_side..=max_side),
+            rng.range(min_side..=max_side),
+        );
+        let area = (width * height) as usize;
+        let mut counts = [0; 6];
+
+        if area > 64 && rng.chance(0.5) {
+            // Every present gets its own 3x3 box.
+            let boxes = (width / 3) * (height / 3);
+            for _ in 0..rng.range(boxes / 2..=boxes) {
+                counts[rng.index(6)] += 1;
+            }
+        } else {
+            let (percent, is_overfull) = if area > 64 {
+                (rng.range(101..=120) as usize, true)
+            } else {
+                (rng.range(60..=110) as usize, false)
+            };
+            let target = area * percent / 100;
+
+            let mut covered = 0;
+            loop {
+                let shape = rng.index(6);
+                if !is_overfull && covered + areas[shape] > target {
+                    break;
+                }
+                counts[shape] += 1;
+                covered += areas[shape];
+                if is_overfull && covered > target {
+                    break;
+                }
+            }
+        }
+
+        let counts: Vec<String> = counts.iter().map(ToString::to_string).collect();
+        input.push_str(&format!("{width}x{height}: {}\n", counts.join(" ")));
+    }
+
+    input
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
+        assert_eq!(result, Some(2));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
+        assert_eq!(result, Some(23));
+    }
+
+    #[test]
+    fn test_generate_size() {
+        let input = generate(20, 7);
+
+        let (shapes, regions) = parse_input(&input).unwrap();
+        assert_eq!(shapes.len(), 6);
+        assert_eq!(regions.len(), 20);
+    }
+
+    #[test]
+    fn test_shapes() {
+        let input = advent_of_code::template::read_file("examples", PUZZLE);
+        let (shapes, regions) = parse_input(&input).unwrap();
+        assert_eq!(shapes.len(), 6);
+        assert_eq!(regions.len(), 3);
+
+        let rendered: String = shapes.iter().map(|shape| format!("{shape}\n")).collect();
+        assert!(input.starts_with(&rendered));
+
+        // An L tromino has four rotations, which are their own reflections.
+        let shape = Shape::new(0, &["#.", "##"]).unwrap();
+        assert_eq!((shape.area(), shape.imbalance()), (3, 1));
+        assert_eq!(shape.orientations.len(), 4);
+        assert_eq!(Shape::new(0, &["###"]).unwrap().orientations.len(), 2);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            parse_input("0:\n#?#\n").unwrap_err(),
+            ParseError::Shape(0, "#?#".to_string())
+        );
+        assert_eq!(
+            parse_input("a:\n###\n").unwrap_err(),
+            ParseError::ShapeId("a:".to_string())
+        );
+        assert_eq!(
+            parse_input("0:\n###\n\n4x: 1\n").unwrap_err(),
+            ParseError::Region("4x: 1".to_string())
+        );
+        assert_eq!(part_one("0:\n###\n\n4x4: one\n"), None);
+    }
+
+    #[test]
+    fn test_packings() {
+        // Small regions are packed by the search, large ones in boxes.
+        for (size, seed) in (1..=10).flat_map(|seed| [(12, seed), (40, seed)]) {
+            let input = generate(size, seed);
+            let (shapes, regions) = parse_input(&input).unwrap();
+            let orientations = shape_orientations(&shapes);
+
+            for region in &regions {
+                let Ok(placements) = pack(region, &shapes, &orientations) else {
+                    continue;
+                };
+
+                let mut counts = vec![0; shapes.len()];
+                let mut covered = HashSet::new();
+                for placement in &placements {
+                    counts[placement.shape] += 1;
+                    assert!(shapes[placement.shape].orientations.iter().any(|cells| {
+                        let (top, left) = placement
+                            .cells
+                            .iter()
+                            .fold((usize::MAX, usize::MAX), |(t, l), &(r, c)| {
+                                (t.min(r), l.min(c))
+                            });
+                        let mut moved: Vec<_> = placement
+                            .cells
+                            .iter()
+                            .map(|(r, c)| (r - top, c - left))
+                            .collect();
+                        moved.sort_unstable();
+                        moved == *cells
+                    }));
+
+                    for &(r, c) in &placement.cells {
+                        assert!(r < region.height && c < region.width);
+                        assert!(covered.insert((r, c)), "presents overlap in {region}");
+                    }
+                }
+                assert_eq!(
+                    counts, region.present_counts,
+                    "presents missing in {region}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_visualize() {
+        let input = advent_of_code::template::read_file("examples", PUZZLE);
+        let text = visualize(&input, Visualization::Text).unwrap();
+
+        assert!(text.starts_with("4x4: 0 0 0 0 2 0 packed\n"));
+        assert!(text.contains("12x5: 1 0 1 0 3 2 rejected by the search: no packing exists\n"));
+        assert!(text.ends_with("2 of 3 regions packed\n"));
+        assert_eq!(text.matches('E').count(), 2 * 7 + 2 * 7);
+
+        let svg = visualize(&input, Visualization::Svg).unwrap();
+        assert!(svg.starts_with("<svg") && svg.ends_with("</svg>\n"));
+        assert!(visualize("0:\n#?\n", Visualization::Text).is_err());
+
+        let (shapes, _) = parse_input(&input).unwrap();
+        let region = parse_region("4x4: 0 0 0 0 3 0").unwrap();
+        assert_eq!(
+            pack(&region, &shapes, &shape_orientations(&shapes)).unwrap_err(),
+            Rejection::Area {
+                required: 21,
+                available: 16
+            }
+        );
+    }
+
+    #[test]
+    fn test_part_one_reference() {
+        advent_of_code::template::differential::assert_agrees(
+            part_one,
+            part_one_reference,
+            generate,
+            100,
+            10,
+        );
+    }
+}
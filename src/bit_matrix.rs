@@ -0,0 +1,482 @@
+/// Module with vectors and matrices over GF(2), the field of bits where addition is XOR.
+use std::{collections::HashMap, ops::BitXorAssign};
+
+const WORD_BITS: usize = u64::BITS as usize;
+
+/// A vector of bits, packed into 64-bit words.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct BitVector {
+    len: usize,
+    /// Bits beyond `len` are always zero, so that words can be compared and counted directly.
+    words: Vec<u64>,
+}
+
+impl BitVector {
+    /// A vector of `len` zeros.
+    pub fn new(len: usize) -> Self {
+        Self {
+            len,
+            words: vec![0; len.div_ceil(WORD_BITS)],
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// # Panics
+    /// Panics if the index is out of bounds.
+    pub fn get(&self, i: usize) -> bool {
+        assert!(i < self.len, "bit {i} is out of bounds.");
+        self.words[i / WORD_BITS] >> (i % WORD_BITS) & 1 == 1
+    }
+
+    pub fn set(&mut self, i: usize, value: bool) {
+        assert!(i < self.len, "bit {i} is out of bounds.");
+        let mask = 1 << (i % WORD_BITS);
+        if value {
+            self.words[i / WORD_BITS] |= mask;
+        } else {
+            self.words[i / WORD_BITS] &= !mask;
+        }
+    }
+
+    pub fn toggle(&mut self, i: usize) {
+        assert!(i < self.len, "bit {i} is out of bounds.");
+        self.words[i / WORD_BITS] ^= 1 << (i % WORD_BITS);
+    }
+
+    /// The number of set bits, i.e. the Hamming weight.
+    pub fn count_ones(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    /// The indices of the set bits in ascending order.
+    pub fn ones(&self) -> impl Iterator<Item = usize> + '_ {
+        self.words.iter().enumerate().flat_map(|(i, &word)| {
+            let mut word = word;
+            std::iter::from_fn(move || {
+                (word != 0).then(|| {
+                    let bit = word.trailing_zeros() as usize;
+                    word &= word - 1;
+                    i * WORD_BITS + bit
+                })
+            })
+        })
+    }
+}
+
+impl BitXorAssign<&BitVector> for BitVector {
+    /// # Panics
+    /// Panics if the vectors have different lengths.
+    fn bitxor_assign(&mut self, rhs: &BitVector) {
+        assert_eq!(self.len, rhs.len, "expected vectors of the same length.");
+        for (a, b) in self.words.iter_mut().zip(&rhs.words) {
+            *a ^= b;
+        }
+    }
+}
+
+impl FromIterator<bool> for BitVector {
+    fn from_iter<I: IntoIterator<Item = bool>>(iter: I) -> Self {
+        let bits: Vec<bool> = iter.into_iter().collect();
+        let mut vector = Self::new(bits.len());
+        for (i, _) in bits.iter().enumerate().filter(|(_, bit)| **bit) {
+            vector.set(i, true);
+        }
+        vector
+    }
+}
+
+/// A matrix of bits, stored row by row with every row packed into 64-bit words.
+///
+/// Solves systems of linear equations over GF(2), e.g. which switches to flip so that lights that are
+/// toggled by several switches end up in a target state:
+///
+/// ```
+/// # use advent_of_code::bit_matrix::{BitMatrix, BitVector};
+/// // Switch 0 toggles lights 0 and 1, switch 1 toggles light 1, switch 2 toggles lights 0 and 1.
+/// let mut switches = BitMatrix::new(2, 3);
+/// for (light, switch) in [(0, 0), (1, 0), (1, 1), (0, 2), (1, 2)] {
+///     switches.set(light, switch, true);
+/// }
+///
+/// let target: BitVector = [true, true].into_iter().collect();
+/// let flips = switches.min_weight_solution(&target).unwrap();
+/// assert_eq!(flips.count_ones(), 1);
+/// assert_eq!(switches.mul_vector(&flips), target);
+/// assert_eq!(switches.rank(), 2);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct BitMatrix {
+    rows: usize,
+    cols: usize,
+    /// The number of words of a row.
+    stride: usize,
+    words: Vec<u64>,
+}
+
+impl BitMatrix {
+    /// A matrix of `rows` by `cols` zeros.
+    pub fn new(rows: usize, cols: usize) -> Self {
+        let stride = cols.div_ceil(WORD_BITS);
+        Self {
+            rows,
+            cols,
+            stride,
+            words: vec![0; rows * stride],
+        }
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    /// # Panics
+    /// Panics if the position is out of bounds.
+    pub fn get(&self, row: usize, col: usize) -> bool {
+        let (word, bit) = self.locate(row, col);
+        self.words[word] >> bit & 1 == 1
+    }
+
+    pub fn set(&mut self, row: usize, col: usize, value: bool) {
+        let (word, bit) = self.locate(row, col);
+        if value {
+            self.words[word] |= 1 << bit;
+        } else {
+            self.words[word] &= !(1 << bit);
+        }
+    }
+
+    pub fn toggle(&mut self, row: usize, col: usize) {
+        let (word, bit) = self.locate(row, col);
+        self.words[word] ^= 1 << bit;
+    }
+
+    fn locate(&self, row: usize, col: usize) -> (usize, usize) {
+        assert!(
+            row < self.rows && col < self.cols,
+            "({row}, {col}) is out of bounds."
+        );
+        (row * self.stride + col / WORD_BITS, col % WORD_BITS)
+    }
+
+    /// The product `Ax`.
+    ///
+    /// # Panics
+    /// Panics if the vector does not have one bit per column.
+    pub fn mul_vector(&self, x: &BitVector) -> BitVector {
+        assert_eq!(x.len(), self.cols, "expected one bit per column.");
+
+        (0..self.rows)
+            .map(|row| {
+                let words = &self.words[row * self.stride..(row + 1) * self.stride];
+                let ones: u32 = words
+                    .iter()
+                    .zip(&x.words)
+                    .map(|(a, b)| (a & b).count_ones())
+                    .sum();
+                ones % 2 == 1
+            })
+            .collect()
+    }
+
+    fn swap_rows(&mut self, a: usize, b: usize) {
+        for word in 0..self.stride {
+            self.words
+                .swap(a * self.stride + word, b * self.stride + word);
+        }
+    }
+
+    /// XOR the row `src` into the row `dst`.
+    fn xor_rows(&mut self, dst: usize, src: usize) {
+        for word in 0..self.stride {
+            let value = self.words[src * self.stride + word];
+            self.words[dst * self.stride + word] ^= value;
+        }
+    }
+
+    /// Transform the matrix into its reduced row echelon form by Gauss-Jordan elimination.
+    /// Returns the pivot column of every non-zero row, in ascending order.
+    pub fn row_reduce(&mut self) -> Vec<usize> {
+        let mut pivots = vec![];
+
+        for col in 0..self.cols {
+            let row = pivots.len();
+            if row == self.rows {
+                break;
+            }
+
+            let Some(pivot) = (row..self.rows).find(|&r| self.get(r, col)) else {
+                continue;
+            };
+            self.swap_rows(row, pivot);
+
+            for r in 0..self.rows {
+                if r != row && self.get(r, col) {
+                    self.xor_rows(r, row);
+                }
+            }
+
+            pivots.push(col);
+        }
+
+        pivots
+    }
+
+    /// The number of linearly independent rows.
+    pub fn rank(&self) -> usize {
+        self.clone().row_reduce().len()
+    }
+
+    /// A basis of the solutions of `Ax = 0`, one vector per free column.
+    pub fn nullspace(&self) -> Vec<BitVector> {
+        let mut reduced = self.clone();
+        let pivots = reduced.row_reduce();
+
+        free_columns(&pivots, self.cols)
+            .map(|free| {
+                let mut x = BitVector::new(self.cols);
+                x.set(free, true);
+                for (row, &pivot) in pivots.iter().enumerate() {
+                    if reduced.get(row, free) {
+                        x.set(pivot, true);
+                    }
+                }
+                x
+            })
+            .collect()
+    }
+
+    /// The reduced row echelon form of `[A | b]` with its pivots, or `None` if `Ax = b` has no solution.
+    fn reduce_augmented(&self, b: &BitVector) -> Option<(BitMatrix, Vec<usize>)> {
+        assert_eq!(b.len(), self.rows, "expected one bit per row.");
+
+        let mut augmented = BitMatrix::new(self.rows, self.cols + 1);
+        for row in 0..self.rows {
+            for col in 0..self.cols {
+                if self.get(row, col) {
+                    augmented.set(row, col, true);
+                }
+            }
+            augmented.set(row, self.cols, b.get(row));
+        }
+
+        let pivots = augmented.row_reduce();
+        // A pivot in the last column is an equation `0 = 1`.
+        (pivots.last() != Some(&self.cols)).then_some((augmented, pivots))
+    }
+
+    /// A solution of `Ax = b`, where every free variable is zero. Returns `None` if there is no solution.
+    ///
+    /// # Panics
+    /// Panics if `b` does not have one bit per row.
+    pub fn solve(&self, b: &BitVector) -> Option<BitVector> {
+        let (augmented, pivots) = self.reduce_augmented(b)?;
+
+        let mut x = BitVector::new(self.cols);
+        for (row, &pivot) in pivots.iter().enumerate() {
+            x.set(pivot, augmented.get(row, self.cols));
+        }
+        Some(x)
+    }
+
+    /// A solution of `Ax = b` with the fewest set bits. Returns `None` if there is no solution.
+    ///
+    /// Every solution is a particular solution plus a combination of the nullspace. The free variables of a
+    /// combination are its own bits, the pivot variables follow from the free ones. Combinations of either half
+    /// of the free variables are enumerated separately (meet in the middle) and grouped by their effect on the
+    /// pivot variables, keeping the lightest combination of every group. This takes `O(2^(k/2) + g²)` for
+    /// `k` free variables and `g ≤ min(2^(k/2), 2^rank)` groups per half, instead of `O(2^k)`.
+    ///
+    /// # Panics
+    /// Panics if `b` does not have one bit per row, or if there are more than 126 free variables.
+    pub fn min_weight_solution(&self, b: &BitVector) -> Option<BitVector> {
+        let (augmented, pivots) = self.reduce_augmented(b)?;
+        let rank = pivots.len();
+        let free: Vec<usize> = free_columns(&pivots, self.cols).collect();
+        assert!(free.len() <= 126, "too many free variables.");
+
+        // How a free variable changes the pivot variables, and the pivot variables if all free ones are zero.
+        let effects: Vec<BitVector> = free
+            .iter()
+            .map(|&col| (0..rank).map(|row| augmented.get(row, col)).collect())
+            .collect();
+        let particular: BitVector = (0..rank).map(|row| augmented.get(row, self.cols)).collect();
+
+        let (left, right) = effects.split_at(effects.len() / 2);
+        let (left, right) = (
+            lightest_combinations(left, rank),
+            lightest_combinations(right, rank),
+        );
+
+        let (mut best, mut best_weight) = ((0, 0, particular.clone()), usize::MAX);
+        for (left_effect, &(left_weight, left_mask)) in &left {
+            for (right_effect, &(right_weight, right_mask)) in &right {
+                let mut pivot_values = particular.clone();
+                pivot_values ^= left_effect;
+                pivot_values ^= right_effect;
+
+                let weight = left_weight + right_weight + pivot_values.count_ones();
+                if weight < best_weight {
+                    best_weight = weight;
+                    best = (left_mask, right_mask, pivot_values);
+                }
+            }
+        }
+
+        let (left_mask, right_mask, pivot_values) = best;
+        let mut x = BitVector::new(self.cols);
+        for (i, &col) in free.iter().enumerate() {
+            let is_set = if i < free.len() / 2 {
+                left_mask >> i & 1 == 1
+            } else {
+                right_mask >> (i - free.len() / 2) & 1 == 1
+            };
+            x.set(col, is_set);
+        }
+        for (row, &pivot) in pivots.iter().enumerate() {
+            x.set(pivot, pivot_values.get(row));
+        }
+
+        Some(x)
+    }
+}
+
+/// The columns without a pivot, in ascending order.
+fn free_columns(pivots: &[usize], cols: usize) -> impl Iterator<Item = usize> + '_ {
+    (0..cols).filter(|col| pivots.binary_search(col).is_err())
+}
+
+/// Every distinct XOR of a subset of effects, with the size and bit mask of the smallest subset that produces it.
+/// Subsets are enumerated in Gray code order, so that every step XORs a single effect.
+fn lightest_combinations(effects: &[BitVector], len: usize) -> HashMap<BitVector, (usize, u64)> {
+    let mut lightest = HashMap::new();
+    let mut effect = BitVector::new(len);
+    lightest.insert(effect.clone(), (0, 0));
+
+    for step in 1..1_u64 << effects.len() {
+        let flipped = step.trailing_zeros() as usize;
+        effect ^= &effects[flipped];
+
+        let mask = step ^ (step >> 1);
+        let weight = mask.count_ones() as usize;
+        lightest
+            .entry(effect.clone())
+            .and_modify(|best: &mut (usize, u64)| {
+                if weight < best.0 {
+                    *best = (weight, mask);
+                }
+            })
+            .or_insert((weight, mask));
+    }
+
+    lightest
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(test)]
+mod tests {
+    use super::{BitMatrix, BitVector};
+    use crate::template::Rng;
+
+    fn random_matrix(rng: &mut Rng, rows: usize, cols: usize) -> BitMatrix {
+        let mut matrix = BitMatrix::new(rows, cols);
+        for row in 0..rows {
+            for col in 0..cols {
+                matrix.set(row, col, rng.chance(0.4));
+            }
+        }
+        matrix
+    }
+
+    fn random_vector(rng: &mut Rng, len: usize) -> BitVector {
+        (0..len).map(|_| rng.chance(0.5)).collect()
+    }
+
+    #[test]
+    fn packs_bits() {
+        let mut x = BitVector::new(130);
+        x.set(0, true);
+        x.set(64, true);
+        x.toggle(129);
+        x.toggle(0);
+
+        assert!(x.get(64) && x.get(129) && !x.get(0));
+        assert_eq!(x.count_ones(), 2);
+        assert_eq!(x.ones().collect::<Vec<_>>(), vec![64, 129]);
+
+        let mut y: BitVector = (0..130).map(|i| i % 64 == 0).collect();
+        y ^= &x;
+        assert_eq!(y.ones().collect::<Vec<_>>(), vec![0, 128, 129]);
+    }
+
+    #[test]
+    fn reduces_rows() {
+        // Rows 0 and 1 add up to row 2.
+        let mut matrix = BitMatrix::new(3, 4);
+        for (row, col) in [(0, 0), (0, 1), (1, 1), (1, 3), (2, 0), (2, 3)] {
+            matrix.set(row, col, true);
+        }
+
+        assert_eq!(matrix.rank(), 2);
+        assert_eq!(matrix.clone().row_reduce(), vec![0, 1]);
+
+        let nullspace = matrix.nullspace();
+        assert_eq!(nullspace.len(), 2);
+        for x in &nullspace {
+            assert_eq!(matrix.mul_vector(x).count_ones(), 0);
+        }
+
+        let b: BitVector = [true, false, false].into_iter().collect();
+        assert_eq!(matrix.solve(&b), None);
+        assert_eq!(matrix.min_weight_solution(&b), None);
+    }
+
+    #[test]
+    fn solves_systems() {
+        let mut rng = Rng::new(3);
+
+        for _ in 0..200 {
+            let (rows, cols) = (rng.index(8) + 1, rng.index(10) + 1);
+            let matrix = random_matrix(&mut rng, rows, cols);
+            let b = matrix.mul_vector(&random_vector(&mut rng, cols));
+
+            let x = matrix.solve(&b).unwrap();
+            assert_eq!(matrix.mul_vector(&x), b);
+
+            // Compare with all 2^cols assignments.
+            let lightest = (0..1_u32 << cols)
+                .map(|mask| (0..cols).map(|i| mask >> i & 1 == 1).collect::<BitVector>())
+                .filter(|x| matrix.mul_vector(x) == b)
+                .map(|x| x.count_ones())
+                .min();
+
+            let x = matrix.min_weight_solution(&b).unwrap();
+            assert_eq!(matrix.mul_vector(&x), b);
+            assert_eq!(Some(x.count_ones()), lightest);
+            assert_eq!(matrix.nullspace().len(), cols - matrix.rank());
+        }
+    }
+
+    #[test]
+    fn scales_past_many_free_variables() {
+        let mut rng = Rng::new(4);
+        let matrix = random_matrix(&mut rng, 6, 40);
+        let b = matrix.mul_vector(&random_vector(&mut rng, 40));
+
+        let x = matrix.min_weight_solution(&b).unwrap();
+        assert_eq!(matrix.mul_vector(&x), b);
+        // Every target of 6 rows is the sum of at most 6 columns of a matrix of rank 6.
+        assert!(x.count_ones() <= 6);
+    }
+}
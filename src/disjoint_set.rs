@@ -0,0 +1,279 @@
+/// Module with a disjoint-set forest (union-find) to track connected components.
+use std::mem;
+
+/// A partition of the elements `0..len` into disjoint components, which can be merged.
+/// Merges use union by size, lookups compress paths, so that both take amortized constant time.
+///
+/// Created [`with_rollback`](DisjointSet::with_rollback), merges can be undone as well, e.g. to answer
+/// offline queries over ranges of edges. Paths are not compressed then, lookups take `O(log n)`.
+///
+/// ```
+/// # use advent_of_code::disjoint_set::DisjointSet;
+/// let mut set = DisjointSet::new(5);
+/// set.union(0, 1);
+/// set.union(3, 4);
+/// set.union(1, 4);
+/// assert!(set.same(0, 3));
+/// assert_eq!(set.component_count(), 2);
+/// assert_eq!(set.components(), vec![vec![0, 1, 3, 4], vec![2]]);
+/// ```
+#[derive(Clone, Debug)]
+pub struct DisjointSet {
+    parent: Vec<usize>,
+    /// The number of elements of a component, only valid for roots.
+    size: Vec<usize>,
+    components: usize,
+    /// The `(child, root)` pairs of every merge, if merges can be undone.
+    history: Option<Vec<(usize, usize)>>,
+}
+
+/// The state of a [`DisjointSet`] to roll back to, see [`DisjointSet::checkpoint`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Checkpoint(usize);
+
+impl DisjointSet {
+    /// `len` elements, each in its own component.
+    pub fn new(len: usize) -> Self {
+        Self {
+            parent: (0..len).collect(),
+            size: vec![1; len],
+            components: len,
+            history: None,
+        }
+    }
+
+    /// `len` elements, each in its own component, where merges can be undone, see [`DisjointSet::rollback`].
+    pub fn with_rollback(len: usize) -> Self {
+        Self {
+            history: Some(vec![]),
+            ..Self::new(len)
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.parent.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.parent.is_empty()
+    }
+
+    /// The representative of the component of an element, compressing the path to it.
+    ///
+    /// # Panics
+    /// Panics if the element is out of bounds.
+    pub fn find(&mut self, x: usize) -> usize {
+        let root = self.root(x);
+
+        if self.history.is_none() {
+            let mut x = x;
+            while self.parent[x] != root {
+                x = mem::replace(&mut self.parent[x], root);
+            }
+        }
+
+        root
+    }
+
+    /// The representative of the component of an element, without compressing the path to it.
+    pub fn root(&self, mut x: usize) -> usize {
+        while self.parent[x] != x {
+            x = self.parent[x];
+        }
+        x
+    }
+
+    /// Merge the components of two elements. Returns `false` if they were in the same component already.
+    pub fn union(&mut self, a: usize, b: usize) -> bool {
+        let (mut a, mut b) = (self.find(a), self.find(b));
+        if a == b {
+            return false;
+        }
+
+        if self.size[a] < self.size[b] {
+            mem::swap(&mut a, &mut b);
+        }
+
+        self.parent[b] = a;
+        self.size[a] += self.size[b];
+        self.components -= 1;
+
+        if let Some(history) = &mut self.history {
+            history.push((b, a));
+        }
+
+        true
+    }
+
+    /// Whether two elements are in the same component.
+    pub fn same(&mut self, a: usize, b: usize) -> bool {
+        self.find(a) == self.find(b)
+    }
+
+    /// The number of elements in the component of an element.
+    pub fn size_of(&mut self, x: usize) -> usize {
+        let root = self.find(x);
+        self.size[root]
+    }
+
+    pub fn component_count(&self) -> usize {
+        self.components
+    }
+
+    /// The sizes of all components, ordered by their smallest element.
+    pub fn component_sizes(&self) -> Vec<usize> {
+        let mut seen = vec![false; self.len()];
+
+        (0..self.len())
+            .filter_map(|x| {
+                let root = self.root(x);
+                (!mem::replace(&mut seen[root], true)).then_some(self.size[root])
+            })
+            .collect()
+    }
+
+    /// The elements of every component in ascending order, ordered by their smallest element.
+    pub fn components(&mut self) -> Vec<Vec<usize>> {
+        let mut indices = vec![usize::MAX; self.len()];
+        let mut components: Vec<Vec<usize>> = Vec::with_capacity(self.components);
+
+        for x in 0..self.len() {
+            let root = self.find(x);
+            if indices[root] == usize::MAX {
+                indices[root] = components.len();
+                components.push(Vec::with_capacity(self.size[root]));
+            }
+            components[indices[root]].push(x);
+        }
+
+        components
+    }
+
+    /// The current state, to undo later merges with [`DisjointSet::rollback`].
+    ///
+    /// # Panics
+    /// Panics if the set was not created [`with_rollback`](DisjointSet::with_rollback).
+    pub fn checkpoint(&self) -> Checkpoint {
+        let history = self.history.as_ref().expect("rollback is not enabled.");
+        Checkpoint(history.len())
+    }
+
+    /// Undo every merge since a checkpoint, in reverse order.
+    ///
+    /// # Panics
+    /// Panics if the set was not created [`with_rollback`](DisjointSet::with_rollback).
+    pub fn rollback(&mut self, Checkpoint(len): Checkpoint) {
+        let history = self.history.as_mut().expect("rollback is not enabled.");
+
+        let start = len.min(history.len());
+
+        for (child, root) in history.drain(start..).rev() {
+            self.parent[child] = child;
+            self.size[root] -= self.size[child];
+            self.components += 1;
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(test)]
+mod tests {
+    use super::DisjointSet;
+    use crate::template::Rng;
+
+    #[test]
+    fn merges_components() {
+        let mut set = DisjointSet::new(6);
+
+        assert!(set.union(0, 1));
+        assert!(set.union(2, 3));
+        assert!(set.union(1, 3));
+        assert!(!set.union(0, 2));
+
+        assert!(set.same(0, 3));
+        assert!(!set.same(0, 4));
+        assert_eq!(set.size_of(2), 4);
+        assert_eq!(set.component_count(), 3);
+        assert_eq!(set.component_sizes(), vec![4, 1, 1]);
+        assert_eq!(set.components(), vec![vec![0, 1, 2, 3], vec![4], vec![5]]);
+    }
+
+    #[test]
+    fn orders_components_by_smallest_element() {
+        // Components of the same size keep the first element as their root, 2 here.
+        let mut set = DisjointSet::new(3);
+        set.union(2, 0);
+
+        assert_eq!(set.root(0), 2);
+        assert_eq!(set.component_sizes(), vec![2, 1]);
+        assert_eq!(set.components(), vec![vec![0, 2], vec![1]]);
+    }
+
+    #[test]
+    fn finds_long_chains() {
+        // NOTE: a recursive `find` would overflow the stack on a chain this long.
+        let len = 1_000_000;
+        let mut set = DisjointSet::new(len);
+        for x in 1..len {
+            set.parent[x] = x - 1;
+        }
+
+        assert_eq!(set.find(len - 1), 0);
+        assert_eq!(set.parent[len - 1], 0);
+        assert_eq!(set.parent[len / 2], 0);
+    }
+
+    #[test]
+    fn rolls_back() {
+        let mut set = DisjointSet::with_rollback(5);
+        set.union(0, 1);
+        let checkpoint = set.checkpoint();
+
+        set.union(1, 2);
+        set.union(3, 4);
+        set.union(2, 4);
+        assert_eq!(set.component_count(), 1);
+
+        set.rollback(checkpoint);
+        assert_eq!(set.component_count(), 4);
+        assert_eq!(
+            set.components(),
+            vec![vec![0, 1], vec![2], vec![3], vec![4]]
+        );
+        assert_eq!(set.size_of(0), 2);
+
+        set.union(2, 3);
+        assert_eq!(set.component_sizes(), vec![2, 2, 1]);
+    }
+
+    #[test]
+    fn agrees_with_labels() {
+        let len = 200;
+
+        for mut set in [DisjointSet::new(len), DisjointSet::with_rollback(len)] {
+            let mut rng = Rng::new(5);
+            let mut labels: Vec<usize> = (0..len).collect();
+
+            for _ in 0..500 {
+                let (a, b) = (rng.index(len), rng.index(len));
+                let (from, to) = (labels[a], labels[b]);
+
+                assert_eq!(set.union(a, b), from != to);
+                labels
+                    .iter_mut()
+                    .filter(|x| **x == from)
+                    .for_each(|x| *x = to);
+
+                let (c, d) = (rng.index(len), rng.index(len));
+                assert_eq!(set.same(c, d), labels[c] == labels[d]);
+            }
+
+            let mut distinct = labels.clone();
+            distinct.sort_unstable();
+            distinct.dedup();
+            assert_eq!(set.component_count(), distinct.len());
+            assert_eq!(set.component_sizes().iter().sum::<usize>(), len);
+        }
+    }
+}
@@ -0,0 +1,385 @@
+/// Module with a two-dimensional grid, the most common shape of puzzle inputs.
+use std::{
+    error::Error,
+    fmt::Display,
+    iter::StepBy,
+    ops::{Index, IndexMut},
+    slice::Iter,
+};
+
+/// Offsets of the four orthogonal neighbours of a cell, clockwise from the top.
+const NEIGHBOURS_4: [(isize, isize); 4] = [(-1, 0), (0, 1), (1, 0), (0, -1)];
+
+/// Offsets of the eight orthogonal and diagonal neighbours of a cell, clockwise from the top left.
+const NEIGHBOURS_8: [(isize, isize); 8] = [
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+    (1, 0),
+    (1, -1),
+    (0, -1),
+];
+
+/// A rectangular grid of cells, stored row by row in one buffer.
+/// Positions are `(row, col)` pairs, with `(0, 0)` in the top left corner.
+///
+/// ```
+/// # use advent_of_code::grid::Grid;
+/// let grid = Grid::parse("#..\n.#.\n").unwrap();
+/// assert_eq!((grid.width(), grid.height()), (3, 2));
+/// assert_eq!(grid[(1, 1)], b'#');
+/// assert_eq!(grid.get(2, 0), None);
+/// assert_eq!(grid.transpose().to_string(), "#.\n.#\n..\n");
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+impl<T> Grid<T> {
+    /// A grid of `width` by `height` cells with the same value.
+    pub fn new(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            cells: vec![value; width * height],
+            width,
+            height,
+        }
+    }
+
+    /// A grid from cells in row-major order. Returns `None` if their number does not match the dimensions.
+    pub fn from_vec(width: usize, height: usize, cells: Vec<T>) -> Option<Self> {
+        (cells.len() == width * height).then_some(Self {
+            cells,
+            width,
+            height,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// The cell at a position, or `None` if the position is outside of the grid.
+    pub fn get(&self, row: usize, col: usize) -> Option<&T> {
+        self.index_of(row, col).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, row: usize, col: usize) -> Option<&mut T> {
+        self.index_of(row, col).map(|i| &mut self.cells[i])
+    }
+
+    /// Whether a position is inside of the grid.
+    pub fn contains(&self, row: usize, col: usize) -> bool {
+        row < self.height && col < self.width
+    }
+
+    /// The index of a position in the row-major buffer, see [`Grid::as_slice`].
+    pub fn index_of(&self, row: usize, col: usize) -> Option<usize> {
+        self.contains(row, col).then_some(row * self.width + col)
+    }
+
+    /// The position of an index in the row-major buffer.
+    pub fn position_of(&self, index: usize) -> (usize, usize) {
+        (index / self.width, index % self.width)
+    }
+
+    /// The position of the first cell that matches a predicate, in reading order.
+    pub fn position(&self, predicate: impl Fn(&T) -> bool) -> Option<(usize, usize)> {
+        let index = self.cells.iter().position(predicate)?;
+        Some(self.position_of(index))
+    }
+
+    /// The cells of a row.
+    ///
+    /// # Panics
+    /// Panics if the row is outside of the grid.
+    pub fn row(&self, row: usize) -> &[T] {
+        assert!(row < self.height, "row {row} is outside of the grid.");
+        &self.cells[row * self.width..(row + 1) * self.width]
+    }
+
+    pub fn row_mut(&mut self, row: usize) -> &mut [T] {
+        assert!(row < self.height, "row {row} is outside of the grid.");
+        &mut self.cells[row * self.width..(row + 1) * self.width]
+    }
+
+    /// The cells of a column, from top to bottom.
+    ///
+    /// # Panics
+    /// Panics if the column is outside of the grid.
+    pub fn column(&self, col: usize) -> StepBy<Iter<'_, T>> {
+        assert!(col < self.width, "column {col} is outside of the grid.");
+        // NOTE: grids without rows have no cells to start a column at.
+        self.cells[col.min(self.cells.len())..]
+            .iter()
+            .step_by(self.width)
+    }
+
+    /// The rows of the grid, from top to bottom.
+    pub fn rows(&self) -> impl DoubleEndedIterator<Item = &[T]> {
+        // NOTE: `chunks_exact` panics on a chunk size of zero, grids without columns have no cells anyway.
+        self.cells.chunks_exact(self.width.max(1))
+    }
+
+    /// The columns of the grid, from left to right, see [`Grid::column`].
+    pub fn columns(&self) -> impl DoubleEndedIterator<Item = StepBy<Iter<'_, T>>> {
+        (0..self.width).map(|col| self.column(col))
+    }
+
+    /// Every position of the grid with its cell, in reading order.
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        self.cells
+            .iter()
+            .enumerate()
+            .map(|(i, cell)| (self.position_of(i), cell))
+    }
+
+    /// The cells in row-major order.
+    pub fn as_slice(&self) -> &[T] {
+        &self.cells
+    }
+
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        &mut self.cells
+    }
+
+    /// The positions of the orthogonal neighbours of a cell that are inside of the grid.
+    pub fn neighbours4(&self, row: usize, col: usize) -> impl Iterator<Item = (usize, usize)> {
+        self.offsets(row, col, &NEIGHBOURS_4)
+    }
+
+    /// The positions of the orthogonal and diagonal neighbours of a cell that are inside of the grid.
+    pub fn neighbours8(&self, row: usize, col: usize) -> impl Iterator<Item = (usize, usize)> {
+        self.offsets(row, col, &NEIGHBOURS_8)
+    }
+
+    fn offsets(
+        &self,
+        row: usize,
+        col: usize,
+        offsets: &'static [(isize, isize)],
+    ) -> impl Iterator<Item = (usize, usize)> {
+        let (width, height) = (self.width, self.height);
+
+        offsets.iter().filter_map(move |&(dr, dc)| {
+            let r = row.checked_add_signed(dr)?;
+            let c = col.checked_add_signed(dc)?;
+            (r < height && c < width).then_some((r, c))
+        })
+    }
+
+    /// A grid of the same dimensions with every cell mapped.
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            cells: self.cells.iter().map(f).collect(),
+            width: self.width,
+            height: self.height,
+        }
+    }
+
+    /// The grid mirrored along its main diagonal, rows become columns.
+    pub fn transpose(&self) -> Self
+    where
+        T: Clone,
+    {
+        let mut cells = Vec::with_capacity(self.cells.len());
+        for col in 0..self.width {
+            cells.extend(self.column(col).cloned());
+        }
+
+        Self {
+            cells,
+            width: self.height,
+            height: self.width,
+        }
+    }
+}
+
+impl Grid<u8> {
+    /// Parse the lines of a puzzle input as rows of bytes.
+    /// Returns an error if the lines have different lengths, see [`Grid::parse_padded`].
+    pub fn parse(input: &str) -> Result<Self, ParseGridError> {
+        let width = input.lines().next().map_or(0, str::len);
+        let mut cells = Vec::with_capacity(input.len());
+
+        for (row, line) in input.lines().enumerate() {
+            if line.len() != width {
+                return Err(ParseGridError {
+                    row,
+                    expected: width,
+                    actual: line.len(),
+                });
+            }
+            cells.extend_from_slice(line.as_bytes());
+        }
+
+        let height = input.lines().count();
+        Ok(Self {
+            cells,
+            width,
+            height,
+        })
+    }
+
+    /// Parse the lines of a puzzle input as rows of bytes, padding short lines with `fill`
+    /// to the length of the longest one, e.g. when editors trimmed trailing whitespace.
+    pub fn parse_padded(input: &str, fill: u8) -> Self {
+        let width = input.lines().map(str::len).max().unwrap_or(0);
+        let mut cells = Vec::with_capacity(input.len());
+        let mut height = 0;
+
+        for line in input.lines() {
+            cells.extend_from_slice(line.as_bytes());
+            cells.resize((height + 1) * width, fill);
+            height += 1;
+        }
+
+        Self {
+            cells,
+            width,
+            height,
+        }
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (row, col): (usize, usize)) -> &T {
+        self.get(row, col)
+            .unwrap_or_else(|| panic!("({row}, {col}) is outside of the grid."))
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (row, col): (usize, usize)) -> &mut T {
+        self.get_mut(row, col)
+            .unwrap_or_else(|| panic!("({row}, {col}) is outside of the grid."))
+    }
+}
+
+/// Renders one line per row, like the puzzle input that the grid was parsed from.
+impl<T: Copy + Into<char>> Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in self.rows() {
+            let line: String = row.iter().map(|&cell| cell.into()).collect();
+            writeln!(f, "{line}")?;
+        }
+        Ok(())
+    }
+}
+
+/// An error which can be returned when parsing a [`Grid`] from lines of different lengths.
+#[derive(Debug, PartialEq, Eq)]
+pub struct ParseGridError {
+    pub row: usize,
+    pub expected: usize,
+    pub actual: usize,
+}
+
+impl Error for ParseGridError {}
+
+impl Display for ParseGridError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "row {} has {} cells, expected {} like the first row",
+            self.row, self.actual, self.expected
+        )
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(test)]
+mod tests {
+    use super::{Grid, ParseGridError};
+
+    #[test]
+    fn parses_rows() {
+        let grid = Grid::parse("abc\ndef\n").unwrap();
+
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid.row(1), b"def");
+        assert_eq!(grid.column(2).copied().collect::<Vec<_>>(), b"cf");
+        assert_eq!(grid.rows().count(), 2);
+        assert_eq!(grid.columns().count(), 3);
+        assert_eq!(grid.to_string(), "abc\ndef\n");
+        assert_eq!(grid.position(|&x| x == b'e'), Some((1, 1)));
+
+        assert_eq!(
+            Grid::parse("abc\nde\n"),
+            Err(ParseGridError {
+                row: 1,
+                expected: 3,
+                actual: 2
+            })
+        );
+        assert!(Grid::parse("").unwrap().is_empty());
+        assert_eq!(Grid::new(3, 0, 0).transpose().height(), 3);
+    }
+
+    #[test]
+    fn pads_ragged_rows() {
+        let grid = Grid::parse_padded("a\nbcd\n\nef", b'.');
+
+        assert_eq!((grid.width(), grid.height()), (3, 4));
+        assert_eq!(grid.to_string(), "a..\nbcd\n...\nef.\n");
+        assert_eq!(Grid::parse_padded("", b'.').height(), 0);
+    }
+
+    #[test]
+    fn checks_indices() {
+        let mut grid = Grid::new(3, 2, 0);
+        grid[(1, 2)] = 5;
+        *grid.get_mut(0, 1).unwrap() = 7;
+
+        assert_eq!(grid.as_slice(), &[0, 7, 0, 0, 0, 5]);
+        assert_eq!(grid.get(2, 0), None);
+        assert_eq!(grid.get(0, 3), None);
+        assert_eq!(grid.index_of(1, 2), Some(5));
+        assert_eq!(grid.position_of(5), (1, 2));
+        assert_eq!(Grid::from_vec(2, 2, vec![1, 2, 3]), None);
+    }
+
+    #[test]
+    fn finds_neighbours() {
+        let grid = Grid::new(3, 3, ());
+
+        assert_eq!(grid.neighbours4(1, 1).count(), 4);
+        assert_eq!(grid.neighbours8(1, 1).count(), 8);
+        assert_eq!(grid.neighbours4(0, 0).collect::<Vec<_>>(), [(0, 1), (1, 0)]);
+        assert_eq!(
+            grid.neighbours8(2, 2).collect::<Vec<_>>(),
+            [(1, 1), (1, 2), (2, 1)]
+        );
+    }
+
+    #[test]
+    fn transposes() {
+        let grid = Grid::parse("ab\ncd\nef\n").unwrap();
+        let transposed = grid.transpose();
+
+        assert_eq!(transposed.to_string(), "ace\nbdf\n");
+        assert_eq!(transposed.transpose(), grid);
+        assert_eq!(
+            grid.map(|&x| x == b'c').iter().filter(|(_, x)| **x).count(),
+            1
+        );
+    }
+}
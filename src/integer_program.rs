@@ -0,0 +1,712 @@
+/// Module with an exact solver for small integer programs `min sum(x)` subject to `Ax = b, x ≥ 0`.
+use std::{error::Error, fmt::Display};
+
+/// An error which can be returned when an [`IntegerProgram`] can not be searched exhaustively.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IntegerProgramError {
+    /// A variable that is not determined by the others has no upper bound.
+    Unbounded(usize),
+    /// A coefficient grew past `i128` during elimination.
+    Overflow,
+}
+
+impl Error for IntegerProgramError {}
+
+impl Display for IntegerProgramError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Unbounded(variable) => write!(
+                f,
+                "variable {variable} is free but has no upper bound, set one with `upper_bound`"
+            ),
+            Self::Overflow => write!(f, "coefficients overflowed during elimination"),
+        }
+    }
+}
+
+/// A system of linear equations over non-negative integer variables, minimizing the sum of the variables.
+///
+/// Variables with a positive coefficient in an equation without negative coefficients are bounded by that
+/// equation. Other variables need an explicit [`upper_bound`](IntegerProgram::upper_bound) if they turn out
+/// to be free.
+///
+/// ```
+/// # use advent_of_code::integer_program::IntegerProgram;
+/// // x0 + x1 = 3, x1 + x2 = 5
+/// let mut program = IntegerProgram::new(3);
+/// program.add_equation(vec![1, 1, 0], 3);
+/// program.add_equation(vec![0, 1, 1], 5);
+/// assert_eq!(program.minimize_sum(), Ok(Some(vec![0, 3, 2])));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct IntegerProgram {
+    variables: usize,
+    /// The coefficients of every equation, followed by its right-hand side.
+    equations: Vec<Vec<i64>>,
+    upper_bounds: Vec<Option<u64>>,
+}
+
+impl IntegerProgram {
+    /// A program over `variables` variables without equations.
+    pub fn new(variables: usize) -> Self {
+        Self {
+            variables,
+            equations: vec![],
+            upper_bounds: vec![None; variables],
+        }
+    }
+
+    /// Add the equation `coefficients · x = rhs`.
+    ///
+    /// # Panics
+    /// Panics if there is not one coefficient per variable.
+    pub fn add_equation(&mut self, mut coefficients: Vec<i64>, rhs: i64) {
+        assert_eq!(
+            coefficients.len(),
+            self.variables,
+            "expected one coefficient per variable."
+        );
+        coefficients.push(rhs);
+        self.equations.push(coefficients);
+    }
+
+    /// Restrict a variable to `x[variable] ≤ bound`.
+    pub fn upper_bound(&mut self, variable: usize, bound: u64) {
+        let current = &mut self.upper_bounds[variable];
+        *current = Some(current.map_or(bound, |b| b.min(bound)));
+    }
+
+    /// The explicit bound of every variable, tightened by equations that have no negative coefficients.
+    /// Variables that appear in no equation are zero in every optimal solution.
+    fn bounds(&self) -> Vec<Option<i128>> {
+        (0..self.variables)
+            .map(|j| {
+                if self.equations.iter().all(|eq| eq[j] == 0) {
+                    return Some(0);
+                }
+
+                let implied = self
+                    .equations
+                    .iter()
+                    .filter(|eq| eq[j] > 0 && eq[..self.variables].iter().all(|&c| c >= 0))
+                    .map(|eq| i128::from(eq[self.variables]).div_euclid(i128::from(eq[j])))
+                    .min();
+                let explicit = self.upper_bounds[j].map(i128::from);
+
+                match (implied, explicit) {
+                    (Some(a), Some(b)) => Some(a.min(b)),
+                    (a, b) => a.or(b),
+                }
+            })
+            .collect()
+    }
+
+    /// A solution with the smallest sum of variables, or `None` if there is no solution.
+    ///
+    /// The equations are brought into reduced row echelon form by fraction-free Gauss-Jordan elimination,
+    /// so that every pivot variable is an integer combination of the free variables, divided by its pivot.
+    /// The free variables are then searched by branch and bound: the largest domain is split in halves, and
+    /// every node narrows the domains to values that leave each pivot variable a chance to be non-negative
+    /// and within bounds, and the sum a chance to beat the best one so far. All arithmetic is on integers,
+    /// and every solution is checked against the original equations.
+    pub fn minimize_sum(&self) -> Result<Option<Vec<u64>>, IntegerProgramError> {
+        // Variables with identical coefficients are interchangeable, only their total matters.
+        let groups = self.identical_columns();
+        if groups.len() < self.variables {
+            let Some(totals) = self.merge(&groups).minimize_sum()? else {
+                return Ok(None);
+            };
+            let solution = self.distribute(&groups, &totals);
+            assert!(self.satisfied_by(&solution), "solution violates equations.");
+            return Ok(Some(solution));
+        }
+
+        let bounds = self.bounds();
+        if bounds.iter().any(|b| b.is_some_and(|b| b < 0)) {
+            return Ok(None);
+        }
+
+        let Some(reduced) = Reduced::new(&self.equations, self.variables)? else {
+            return Ok(None);
+        };
+
+        if let Some(&unbounded) = reduced.free.iter().find(|&&j| bounds[j].is_none()) {
+            return Err(IntegerProgramError::Unbounded(unbounded));
+        }
+
+        let mut search = Search::new(self, &reduced, &bounds)?;
+        let domains = reduced
+            .free
+            .iter()
+            .map(|&f| (0, bounds[f].unwrap()))
+            .collect();
+        search.run(domains);
+
+        let Some(values) = search.best else {
+            return Ok(None);
+        };
+
+        let solution = reduced.solution(&values);
+        assert!(self.satisfied_by(&solution), "solution violates equations.");
+        Ok(Some(solution))
+    }
+
+    /// The variables grouped by their coefficients, ordered by their first variable.
+    fn identical_columns(&self) -> Vec<Vec<usize>> {
+        let column = |j: usize| self.equations.iter().map(move |eq| eq[j]);
+        let mut groups: Vec<Vec<usize>> = vec![];
+
+        for j in 0..self.variables {
+            match groups
+                .iter_mut()
+                .find(|group| column(group[0]).eq(column(j)))
+            {
+                Some(group) => group.push(j),
+                None => groups.push(vec![j]),
+            }
+        }
+
+        groups
+    }
+
+    /// A program with one variable per group, that is bounded by the sum of the bounds of the group.
+    fn merge(&self, groups: &[Vec<usize>]) -> IntegerProgram {
+        let mut merged = IntegerProgram::new(groups.len());
+
+        for eq in &self.equations {
+            let coefficients = groups.iter().map(|group| eq[group[0]]).collect();
+            merged.add_equation(coefficients, eq[self.variables]);
+        }
+
+        for (i, group) in groups.iter().enumerate() {
+            let bound = group.iter().try_fold(0_u64, |sum, &j| {
+                Some(sum.saturating_add(self.upper_bounds[j]?))
+            });
+            if let Some(bound) = bound {
+                merged.upper_bound(i, bound);
+            }
+        }
+
+        merged
+    }
+
+    /// Split the total of every group over its variables, filling them up to their bounds in order.
+    fn distribute(&self, groups: &[Vec<usize>], totals: &[u64]) -> Vec<u64> {
+        let mut x = vec![0; self.variables];
+
+        for (group, &total) in groups.iter().zip(totals) {
+            let mut left = total;
+            for &j in group {
+                x[j] = self.upper_bounds[j].map_or(left, |bound| bound.min(left));
+                left -= x[j];
+            }
+        }
+
+        x
+    }
+
+    /// Whether a solution satisfies every equation and bound exactly.
+    pub fn satisfied_by(&self, x: &[u64]) -> bool {
+        x.len() == self.variables
+            && x.iter()
+                .zip(&self.upper_bounds)
+                .all(|(&x, bound)| bound.is_none_or(|b| x <= b))
+            && self.equations.iter().all(|eq| {
+                let lhs: i128 = (0..self.variables)
+                    .map(|j| i128::from(eq[j]) * i128::from(x[j]))
+                    .sum();
+                lhs == i128::from(eq[self.variables])
+            })
+    }
+}
+
+/// Equations in reduced row echelon form, `pivot · x[pivot_column] + Σ coefficient · x[free] = rhs`.
+struct Reduced {
+    variables: usize,
+    /// The pivot column and the positive pivot of every equation.
+    pivots: Vec<(usize, i128)>,
+    free: Vec<usize>,
+    /// The coefficient of every free variable in every equation.
+    coefficients: Vec<Vec<i128>>,
+    rhs: Vec<i128>,
+}
+
+impl Reduced {
+    /// Returns `None` if elimination leaves an equation `0 = rhs` with a non-zero right-hand side.
+    fn new(equations: &[Vec<i64>], variables: usize) -> Result<Option<Self>, IntegerProgramError> {
+        let mut rows: Vec<Vec<i128>> = equations
+            .iter()
+            .map(|eq| eq.iter().map(|&c| i128::from(c)).collect())
+            .collect();
+        let mut pivots = vec![];
+
+        for col in 0..variables {
+            let rank = pivots.len();
+            // The smallest pivot keeps the multipliers small.
+            let Some(pivot) = (rank..rows.len())
+                .filter(|&r| rows[r][col] != 0)
+                .min_by_key(|&r| rows[r][col].unsigned_abs())
+            else {
+                continue;
+            };
+            rows.swap(rank, pivot);
+            if rows[rank][col] < 0 {
+                rows[rank].iter_mut().for_each(|c| *c = -*c);
+            }
+
+            let pivot_row = rows[rank].clone();
+            for (r, row) in rows.iter_mut().enumerate() {
+                if r == rank || row[col] == 0 {
+                    continue;
+                }
+                let g = gcd(pivot_row[col], row[col]);
+                let (scale, factor) = (pivot_row[col] / g, row[col] / g);
+
+                for (value, &p) in row.iter_mut().zip(&pivot_row) {
+                    *value = scale
+                        .checked_mul(*value)
+                        .zip(factor.checked_mul(p))
+                        .and_then(|(a, b)| a.checked_sub(b))
+                        .ok_or(IntegerProgramError::Overflow)?;
+                }
+                normalize(row);
+            }
+
+            pivots.push(col);
+        }
+
+        if rows[pivots.len()..].iter().any(|row| row[variables] != 0) {
+            return Ok(None);
+        }
+
+        let free: Vec<usize> = (0..variables).filter(|col| !pivots.contains(col)).collect();
+        let coefficients = (0..pivots.len())
+            .map(|r| free.iter().map(|&f| rows[r][f]).collect())
+            .collect();
+        let rhs = (0..pivots.len()).map(|r| rows[r][variables]).collect();
+        // NOTE: eliminating later columns scales the rows of earlier pivots, so they are read last.
+        let pivots = pivots
+            .into_iter()
+            .enumerate()
+            .map(|(r, col)| (col, rows[r][col]))
+            .collect();
+
+        Ok(Some(Self {
+            variables,
+            pivots,
+            free,
+            coefficients,
+            rhs,
+        }))
+    }
+
+    /// The value of every variable, given the values of the free variables.
+    fn solution(&self, values: &[i128]) -> Vec<u64> {
+        let mut x = vec![0; self.variables];
+        for (&f, &value) in self.free.iter().zip(values) {
+            x[f] = value as u64;
+        }
+        for (r, &(col, pivot)) in self.pivots.iter().enumerate() {
+            let rest: i128 = (0..self.free.len())
+                .map(|k| self.coefficients[r][k] * values[k])
+                .sum();
+            x[col] = ((self.rhs[r] - rest) / pivot) as u64;
+        }
+        x
+    }
+}
+
+/// A constraint `lower ≤ Σ coefficient · x[free] ≤ upper` on the free variables of [`Reduced`] equations.
+struct Constraint {
+    coefficients: Vec<i128>,
+    lower: Option<i128>,
+    upper: i128,
+}
+
+/// The branch and bound search over the free variables of [`Reduced`] equations.
+struct Search<'a> {
+    reduced: &'a Reduced,
+    /// `pivot · bound` of the pivot variable of every equation, if it is bounded.
+    pivot_limits: Vec<Option<i128>>,
+    /// The sum of variables, scaled by the least common multiple of the pivots, is `Σ weight · x[free] + offset`.
+    weights: Vec<i128>,
+    /// One constraint per equation that keeps its pivot variable within bounds, followed by constraints that
+    /// bound the sum of variables.
+    constraints: Vec<Constraint>,
+    /// `(scale, shift)` of every constraint that bounds the sum, such that `upper = scale · sum - shift`.
+    sum_bounds: Vec<(i128, i128)>,
+    best: Option<Vec<i128>>,
+}
+
+impl<'a> Search<'a> {
+    /// The number of rounds of propagation per node. More rounds rarely narrow the domains further.
+    const ROUNDS: usize = 8;
+
+    fn new(
+        program: &IntegerProgram,
+        reduced: &'a Reduced,
+        bounds: &[Option<i128>],
+    ) -> Result<Self, IntegerProgramError> {
+        let overflow = IntegerProgramError::Overflow;
+        let free_bounds: Vec<i128> = reduced.free.iter().map(|&f| bounds[f].unwrap()).collect();
+        let pivot_limits: Vec<Option<i128>> = reduced
+            .pivots
+            .iter()
+            .map(|&(col, pivot)| bounds[col].map(|b| b.checked_mul(pivot).ok_or(overflow)))
+            .map(Option::transpose)
+            .collect::<Result<_, _>>()?;
+
+        let lcm = reduced
+            .pivots
+            .iter()
+            .try_fold(1_i128, |lcm, &(_, pivot)| {
+                (lcm / gcd(lcm, pivot)).checked_mul(pivot)
+            })
+            .ok_or(overflow)?;
+
+        // Every pivot variable is `(rhs - Σ coefficient · x[free]) / pivot`.
+        let mut weights = vec![lcm; reduced.free.len()];
+        let mut offset = 0_i128;
+        for (r, &(_, pivot)) in reduced.pivots.iter().enumerate() {
+            let scale = lcm / pivot;
+            offset = scale
+                .checked_mul(reduced.rhs[r])
+                .and_then(|x| x.checked_add(offset))
+                .ok_or(overflow)?;
+            for (weight, &c) in weights.iter_mut().zip(&reduced.coefficients[r]) {
+                *weight = scale
+                    .checked_mul(c)
+                    .and_then(|x| weight.checked_sub(x))
+                    .ok_or(overflow)?;
+            }
+        }
+
+        let mut constraints: Vec<Constraint> = reduced
+            .coefficients
+            .iter()
+            .zip(&reduced.rhs)
+            .zip(&pivot_limits)
+            .map(|((coefficients, &rhs), limit)| Constraint {
+                coefficients: coefficients.clone(),
+                lower: limit.map(|limit| rhs - limit),
+                upper: rhs,
+            })
+            .collect();
+        let mut sum_bounds = vec![(lcm, offset)];
+        constraints.push(Constraint {
+            coefficients: weights.clone(),
+            lower: None,
+            upper: range(&weights, &free_bounds)?.1,
+        });
+
+        // An equation without negative coefficients needs at least `(rhs - Σ a · x[free]) / max(a)` of the
+        // pivot variables, where `max(a)` is the largest coefficient of a pivot variable. So the sum is at least
+        // `Σ x[free] + (rhs - Σ a · x[free]) / max(a)`.
+        let variables = program.variables;
+        for equation in &program.equations {
+            let largest = reduced.pivots.iter().map(|&(col, _)| equation[col]).max();
+            let Some(largest) = largest.filter(|&a| a > 0).map(i128::from) else {
+                continue;
+            };
+            if equation[..variables].iter().any(|&a| a < 0) {
+                continue;
+            }
+
+            let coefficients: Vec<i128> = reduced
+                .free
+                .iter()
+                .map(|&f| largest - i128::from(equation[f]))
+                .collect();
+            sum_bounds.push((largest, i128::from(equation[variables])));
+            constraints.push(Constraint {
+                upper: range(&coefficients, &free_bounds)?.1,
+                coefficients,
+                lower: None,
+            });
+        }
+
+        // NOTE: the ranges bound every intermediate value of the search, so that it can use unchecked arithmetic.
+        for constraint in &constraints {
+            range(&constraint.coefficients, &free_bounds)?;
+        }
+
+        Ok(Self {
+            reduced,
+            pivot_limits,
+            weights,
+            constraints,
+            sum_bounds,
+            best: None,
+        })
+    }
+
+    /// Narrow the domains of the free variables to values that can satisfy every constraint on its own,
+    /// given the domains of the other variables. Returns `false` if a domain becomes empty.
+    fn propagate(&self, domains: &mut [(i128, i128)]) -> bool {
+        for _ in 0..Self::ROUNDS {
+            let mut changed = false;
+
+            for constraint in &self.constraints {
+                let (min, max) = constraint
+                    .coefficients
+                    .iter()
+                    .enumerate()
+                    .map(|(k, &c)| extremes(c, domains[k]))
+                    .fold((0, 0), |(min, max), (lo, hi)| (min + lo, max + hi));
+
+                if min > constraint.upper || constraint.lower.is_some_and(|lower| max < lower) {
+                    return false;
+                }
+
+                for (k, &c) in constraint.coefficients.iter().enumerate() {
+                    if c == 0 {
+                        continue;
+                    }
+
+                    // `c · x` can take up the slack that the other terms leave at their extremes.
+                    let (term_min, term_max) = extremes(c, domains[k]);
+                    let below = constraint.upper - (min - term_min);
+                    let above = constraint.lower.map(|lower| lower - (max - term_max));
+
+                    let (lo, hi) = domains[k];
+                    let (new_lo, new_hi) = if c > 0 {
+                        (
+                            above.map_or(lo, |above| lo.max(div_ceil(above, c))),
+                            hi.min(div_floor(below, c)),
+                        )
+                    } else {
+                        (
+                            lo.max(div_ceil(below, c)),
+                            above.map_or(hi, |above| hi.min(div_floor(above, c))),
+                        )
+                    };
+
+                    if new_lo > new_hi {
+                        return false;
+                    }
+                    if (new_lo, new_hi) != (lo, hi) {
+                        domains[k] = (new_lo, new_hi);
+                        changed = true;
+                    }
+                }
+            }
+
+            if !changed {
+                break;
+            }
+        }
+
+        true
+    }
+
+    /// Search the domains, splitting the largest domain in halves and trying the half with the smaller sum first.
+    fn run(&mut self, mut domains: Vec<(i128, i128)>) {
+        if !self.propagate(&mut domains) {
+            return;
+        }
+
+        let split = (0..domains.len())
+            .filter(|&k| domains[k].0 < domains[k].1)
+            .max_by_key(|&k| domains[k].1 - domains[k].0);
+        let Some(k) = split else {
+            self.accept(domains.iter().map(|&(value, _)| value).collect());
+            return;
+        };
+
+        let (lo, hi) = domains[k];
+        let mid = lo + (hi - lo) / 2;
+        let mut halves = [(lo, mid), (mid + 1, hi)];
+        if self.weights[k] < 0 {
+            halves.reverse();
+        }
+
+        for half in halves {
+            let mut domains = domains.clone();
+            domains[k] = half;
+            self.run(domains);
+        }
+    }
+
+    /// Record an assignment if every pivot variable is a non-negative integer within its bounds.
+    /// Only assignments with a smaller sum than the best one reach this point.
+    fn accept(&mut self, values: Vec<i128>) {
+        let mut sum: i128 = values.iter().sum();
+
+        for (r, &(_, pivot)) in self.reduced.pivots.iter().enumerate() {
+            let residual: i128 = self.reduced.rhs[r]
+                - (0..values.len())
+                    .map(|k| self.reduced.coefficients[r][k] * values[k])
+                    .sum::<i128>();
+            let in_bounds = self.pivot_limits[r].is_none_or(|limit| residual <= limit);
+            if residual < 0 || residual % pivot != 0 || !in_bounds {
+                return;
+            }
+            sum += residual / pivot;
+        }
+
+        // Later assignments have to beat this one by at least one.
+        let bounded = self.reduced.pivots.len();
+        for (constraint, &(scale, shift)) in
+            self.constraints[bounded..].iter_mut().zip(&self.sum_bounds)
+        {
+            constraint.upper = scale * (sum - 1) - shift;
+        }
+        self.best = Some(values);
+    }
+}
+
+/// The smallest and largest value of `c · x` with `lo ≤ x ≤ hi`.
+fn extremes(c: i128, (lo, hi): (i128, i128)) -> (i128, i128) {
+    ((c * lo).min(c * hi), (c * lo).max(c * hi))
+}
+
+/// The range of `Σ coefficient · x[k]` with `0 ≤ x[k] ≤ bound[k]`.
+fn range(coefficients: &[i128], bounds: &[i128]) -> Result<(i128, i128), IntegerProgramError> {
+    coefficients
+        .iter()
+        .zip(bounds)
+        .try_fold((0_i128, 0_i128), |(lo, hi), (&c, &bound)| {
+            let extreme = c.checked_mul(bound)?;
+            Some((
+                lo.checked_add(extreme.min(0))?,
+                hi.checked_add(extreme.max(0))?,
+            ))
+        })
+        .ok_or(IntegerProgramError::Overflow)
+}
+
+fn gcd(a: i128, b: i128) -> i128 {
+    let (mut a, mut b) = (a.unsigned_abs(), b.unsigned_abs());
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a as i128
+}
+
+fn div_floor(a: i128, b: i128) -> i128 {
+    let q = a / b;
+    if a % b != 0 && (a < 0) != (b < 0) {
+        q - 1
+    } else {
+        q
+    }
+}
+
+fn div_ceil(a: i128, b: i128) -> i128 {
+    let q = a / b;
+    if a % b != 0 && (a < 0) == (b < 0) {
+        q + 1
+    } else {
+        q
+    }
+}
+
+/// Divide a row by the greatest common divisor of its entries.
+fn normalize(row: &mut [i128]) {
+    let g = row.iter().fold(0, |g, &c| gcd(g, c));
+    if g > 1 {
+        row.iter_mut().for_each(|c| *c /= g);
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(test)]
+mod tests {
+    use super::{IntegerProgram, IntegerProgramError};
+    use crate::template::Rng;
+
+    /// The smallest sum of all assignments with `x[j] ≤ bound`.
+    fn brute_force(program: &IntegerProgram, bound: u64) -> Option<u64> {
+        let mut x = vec![0; program.variables];
+        let mut best = None;
+
+        loop {
+            if program.satisfied_by(&x) {
+                let sum = x.iter().sum::<u64>();
+                best = Some(best.map_or(sum, |b: u64| b.min(sum)));
+            }
+
+            let Some(j) = x.iter().position(|&v| v < bound) else {
+                return best;
+            };
+            x[j] += 1;
+            x[..j].iter_mut().for_each(|v| *v = 0);
+        }
+    }
+
+    #[test]
+    fn solves_exactly() {
+        let mut program = IntegerProgram::new(2);
+        program.add_equation(vec![2, 0], 3);
+        assert_eq!(program.minimize_sum(), Ok(None));
+
+        // The optimum of the relaxation is x = (7/3, 0) with sum 7/3.
+        let mut program = IntegerProgram::new(2);
+        program.add_equation(vec![3, 2], 7);
+        assert_eq!(program.minimize_sum(), Ok(Some(vec![1, 2])));
+
+        let mut program = IntegerProgram::new(2);
+        program.add_equation(vec![1, -1], 2);
+        assert_eq!(
+            program.minimize_sum(),
+            Err(IntegerProgramError::Unbounded(1))
+        );
+        program.upper_bound(1, 10);
+        assert_eq!(program.minimize_sum(), Ok(Some(vec![2, 0])));
+
+        let mut program = IntegerProgram::new(1);
+        program.add_equation(vec![1], -1);
+        assert_eq!(program.minimize_sum(), Ok(None));
+    }
+
+    #[test]
+    fn merges_identical_columns() {
+        let mut program = IntegerProgram::new(4);
+        program.add_equation(vec![1, 1, 2, 1], 5);
+        program.add_equation(vec![0, 0, 1, 1], 2);
+        program.upper_bound(0, 0);
+        assert_eq!(program.minimize_sum(), Ok(Some(vec![0, 1, 2, 0])));
+
+        // The identical columns would have to add up to 2.
+        program.upper_bound(2, 1);
+        program.upper_bound(1, 1);
+        assert_eq!(program.minimize_sum(), Ok(None));
+
+        let mut program = IntegerProgram::new(3);
+        program.add_equation(vec![1, 1, 1], 4);
+        program.upper_bound(0, 1);
+        program.upper_bound(1, 2);
+        assert_eq!(program.minimize_sum(), Ok(Some(vec![1, 2, 1])));
+    }
+
+    #[test]
+    fn agrees_with_brute_force() {
+        let mut rng = Rng::new(8);
+        let bound = 6;
+
+        for _ in 0..300 {
+            let (rows, variables) = (rng.index(3) + 1, rng.index(4) + 1);
+            let signed = rng.chance(0.3);
+            let mut program = IntegerProgram::new(variables);
+
+            let x: Vec<i64> = (0..variables)
+                .map(|_| rng.range(0..=bound) as i64)
+                .collect();
+            for _ in 0..rows {
+                let coefficients: Vec<i64> = (0..variables)
+                    .map(|_| rng.range(0..=3) as i64 - if signed { 1 } else { 0 })
+                    .collect();
+                let rhs = coefficients.iter().zip(&x).map(|(c, x)| c * x).sum();
+                program.add_equation(coefficients, rhs);
+            }
+            for j in 0..variables {
+                program.upper_bound(j, bound);
+            }
+
+            let solution = program.minimize_sum().unwrap().unwrap();
+            assert!(program.satisfied_by(&solution));
+            assert_eq!(Some(solution.iter().sum()), brute_force(&program, bound));
+        }
+    }
+}
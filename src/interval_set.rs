@@ -0,0 +1,313 @@
+/// Module with a set of integers that is stored as sorted, disjoint intervals.
+use std::{fmt::Debug, ops::RangeInclusive};
+
+/// Integers that an [`IntervalSet`] can hold.
+pub trait Integer: Copy + Ord + Debug {
+    fn checked_successor(self) -> Option<Self>;
+    fn checked_predecessor(self) -> Option<Self>;
+    /// The number of integers from `start` to `end`, inclusive.
+    fn count(start: Self, end: Self) -> u128;
+}
+
+macro_rules! impl_integer {
+    ($($t:ty),*) => {$(
+        impl Integer for $t {
+            fn checked_successor(self) -> Option<Self> {
+                self.checked_add(1)
+            }
+
+            fn checked_predecessor(self) -> Option<Self> {
+                self.checked_sub(1)
+            }
+
+            #[allow(clippy::cast_sign_loss, clippy::cast_lossless)]
+            fn count(start: Self, end: Self) -> u128 {
+                (end as i128 - start as i128 + 1) as u128
+            }
+        }
+    )*};
+}
+
+impl_integer!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+
+/// A set of integers, stored as sorted intervals that neither overlap nor touch.
+/// Ranges are merged when they are inserted, so that lookups take `O(log n)` for `n` intervals.
+///
+/// ```
+/// # use advent_of_code::interval_set::IntervalSet;
+/// let fresh: IntervalSet<u64> = [3..=5, 10..=14, 16..=20, 12..=18].into_iter().collect();
+/// assert_eq!(fresh.iter().collect::<Vec<_>>(), vec![3..=5, 10..=20]);
+/// assert!(fresh.contains(11) && !fresh.contains(8));
+/// assert_eq!(fresh.total_len(), 14);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct IntervalSet<T> {
+    /// Inclusive `(start, end)` pairs, sorted and separated by at least one missing integer.
+    intervals: Vec<(T, T)>,
+}
+
+impl<T: Integer> IntervalSet<T> {
+    pub fn new() -> Self {
+        Self { intervals: vec![] }
+    }
+
+    /// Whether the set holds no integers.
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    /// The number of disjoint intervals.
+    pub fn interval_count(&self) -> usize {
+        self.intervals.len()
+    }
+
+    /// The number of integers in the set.
+    pub fn total_len(&self) -> u128 {
+        self.intervals
+            .iter()
+            .map(|&(start, end)| T::count(start, end))
+            .sum()
+    }
+
+    /// Whether the set holds an integer, by binary search.
+    pub fn contains(&self, x: T) -> bool {
+        let i = self.intervals.partition_point(|&(_, end)| end < x);
+        self.intervals.get(i).is_some_and(|&(start, _)| start <= x)
+    }
+
+    /// Add the integers of a range, merging it with the intervals that it overlaps or touches.
+    /// Empty ranges are ignored.
+    pub fn insert(&mut self, range: RangeInclusive<T>) {
+        let (mut start, mut end) = range.into_inner();
+        if start > end {
+            return;
+        }
+
+        // The intervals from `first` to `last` (exclusive) overlap or touch the range.
+        let first = self
+            .intervals
+            .partition_point(|&(_, e)| e.checked_successor().is_some_and(|e| e < start));
+        let last = self
+            .intervals
+            .partition_point(|&(s, _)| s.checked_predecessor().is_none_or(|s| s <= end));
+
+        if first < last {
+            start = start.min(self.intervals[first].0);
+            end = end.max(self.intervals[last - 1].1);
+        }
+
+        self.intervals.splice(first..last, [(start, end)]);
+    }
+
+    /// The intervals of the set in ascending order.
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = RangeInclusive<T>> {
+        self.intervals.iter().map(|&(start, end)| start..=end)
+    }
+
+    /// The integers that are in either set.
+    pub fn union(&self, other: &Self) -> Self {
+        let mut intervals: Vec<(T, T)> =
+            Vec::with_capacity(self.intervals.len() + other.intervals.len());
+        let (mut a, mut b) = (
+            self.intervals.iter().peekable(),
+            other.intervals.iter().peekable(),
+        );
+
+        // NOTE: merging in order of starts keeps the result sorted, so touching intervals only
+        // have to be merged with the last one.
+        while let Some(&(start, end)) = match (a.peek(), b.peek()) {
+            (Some(x), Some(y)) if x.0 <= y.0 => a.next(),
+            (Some(_), Some(_)) | (None, Some(_)) => b.next(),
+            (Some(_), None) => a.next(),
+            (None, None) => None,
+        } {
+            push_merged(&mut intervals, start, end);
+        }
+
+        Self { intervals }
+    }
+
+    /// The integers that are in both sets.
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut intervals = vec![];
+        let (mut i, mut j) = (0, 0);
+
+        while let (Some(&(a_start, a_end)), Some(&(b_start, b_end))) =
+            (self.intervals.get(i), other.intervals.get(j))
+        {
+            let (start, end) = (a_start.max(b_start), a_end.min(b_end));
+            if start <= end {
+                intervals.push((start, end));
+            }
+
+            // The interval that ends first can not overlap any later interval of the other set.
+            if a_end < b_end {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+
+        Self { intervals }
+    }
+
+    /// The integers that are in this set, but not in the other.
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut intervals = vec![];
+        let mut j = 0;
+
+        for &(start, end) in &self.intervals {
+            // Skip the intervals of the other set that end before this one.
+            while other.intervals.get(j).is_some_and(|&(_, e)| e < start) {
+                j += 1;
+            }
+
+            // Cut the intervals of the other set out of this one, `start` is the start of the remainder.
+            let mut start = Some(start);
+
+            let mut k = j;
+            while let (Some(s), Some(&(cut_start, cut_end))) = (start, other.intervals.get(k)) {
+                if cut_start > end {
+                    break;
+                }
+                if cut_start > s {
+                    intervals.push((s, cut_start.checked_predecessor().unwrap_or(s)));
+                }
+                start = cut_end.checked_successor().filter(|&s| s <= end);
+                k += 1;
+            }
+
+            if let Some(s) = start {
+                intervals.push((s, end));
+            }
+        }
+
+        Self { intervals }
+    }
+}
+
+/// Append an interval that starts at or after the start of the last one, merging them if they overlap or touch.
+fn push_merged<T: Integer>(intervals: &mut Vec<(T, T)>, start: T, end: T) {
+    match intervals.last_mut() {
+        Some((_, last_end)) if last_end.checked_successor().is_none_or(|e| start <= e) => {
+            *last_end = end.max(*last_end);
+        }
+        _ => intervals.push((start, end)),
+    }
+}
+
+impl<T: Integer> FromIterator<RangeInclusive<T>> for IntervalSet<T> {
+    /// Sorts the ranges once and merges them in a single pass.
+    fn from_iter<I: IntoIterator<Item = RangeInclusive<T>>>(iter: I) -> Self {
+        let mut ranges: Vec<(T, T)> = iter
+            .into_iter()
+            .map(RangeInclusive::into_inner)
+            .filter(|(start, end)| start <= end)
+            .collect();
+        ranges.sort_unstable();
+
+        let mut intervals: Vec<(T, T)> = Vec::with_capacity(ranges.len());
+
+        for (start, end) in ranges {
+            push_merged(&mut intervals, start, end);
+        }
+
+        Self { intervals }
+    }
+}
+
+impl<T: Integer> Extend<RangeInclusive<T>> for IntervalSet<T> {
+    fn extend<I: IntoIterator<Item = RangeInclusive<T>>>(&mut self, iter: I) {
+        *self = self.union(&iter.into_iter().collect());
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(test)]
+mod tests {
+    use super::IntervalSet;
+    use crate::template::Rng;
+    use std::collections::BTreeSet;
+
+    fn set(ranges: &[(u8, u8)]) -> IntervalSet<u8> {
+        ranges.iter().map(|&(start, end)| start..=end).collect()
+    }
+
+    fn members(set: &IntervalSet<u8>) -> BTreeSet<u8> {
+        set.iter().flatten().collect()
+    }
+
+    #[test]
+    fn normalizes_ranges() {
+        let set = set(&[(10, 14), (3, 5), (16, 20), (12, 18), (6, 6), (30, 29)]);
+
+        assert_eq!(set.iter().collect::<Vec<_>>(), vec![3..=6, 10..=20]);
+        assert_eq!(set.interval_count(), 2);
+        assert_eq!(set.total_len(), 15);
+        assert!(IntervalSet::<u8>::new().is_empty());
+    }
+
+    #[test]
+    #[allow(clippy::reversed_empty_ranges)]
+    fn inserts_ranges() {
+        let mut set = IntervalSet::new();
+        set.insert(10..=12);
+        set.insert(20..=22);
+        set.insert(0..=1);
+        set.insert(13..=19);
+        set.insert(5..=3);
+
+        assert_eq!(set.iter().collect::<Vec<_>>(), vec![0..=1, 10..=22]);
+
+        set.insert(u8::MAX..=u8::MAX);
+        set.insert(0..=u8::MAX);
+        assert_eq!(set.iter().collect::<Vec<_>>(), vec![0..=u8::MAX]);
+        assert_eq!(set.total_len(), 256);
+    }
+
+    #[test]
+    fn finds_members() {
+        let set = set(&[(3, 5), (10, 20)]);
+
+        assert!(set.contains(3) && set.contains(5) && set.contains(15) && set.contains(20));
+        assert!(!set.contains(2) && !set.contains(6) && !set.contains(21));
+    }
+
+    #[test]
+    fn combines_sets() {
+        let mut rng = Rng::new(9);
+        let mut random = || -> IntervalSet<u8> {
+            (0..rng.range(0..=6))
+                .map(|_| {
+                    let start = rng.range(0..=255) as u8;
+                    start..=start.saturating_add(rng.range(0..=40) as u8)
+                })
+                .collect()
+        };
+
+        for _ in 0..500 {
+            let (a, b) = (random(), random());
+            let (x, y) = (members(&a), members(&b));
+
+            let union = a.union(&b);
+            let intersection = a.intersection(&b);
+            let difference = a.difference(&b);
+
+            assert_eq!(members(&union), &x | &y);
+            assert_eq!(members(&intersection), &x & &y);
+            assert_eq!(members(&difference), &x - &y);
+
+            // Results are normalized like sets built from their members.
+            for result in [union, intersection, difference] {
+                let normalized: IntervalSet<u8> =
+                    members(&result).into_iter().map(|x| x..=x).collect();
+                assert_eq!(result, normalized);
+            }
+
+            let mut extended = a.clone();
+            extended.extend(b.iter());
+            assert_eq!(extended, a.union(&b));
+        }
+    }
+}
@@ -1,3 +1,8 @@
+pub mod bit_matrix;
+pub mod disjoint_set;
+pub mod grid;
+pub mod integer_program;
+pub mod interval_set;
 pub mod template;
 
 // Use this file to add helper functions and additional modules.
@@ -1,81 +1,164 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve, time};
+use advent_of_code::template::commands::{all, download, generate, read, scaffold, solve, time};
 use args::{AppArguments, parse};
 
+#[cfg(feature = "dhat-heap")]
+use advent_of_code::template::Solution;
+#[cfg(not(feature = "dhat-heap"))]
+use registry::SOLUTIONS;
+
 #[cfg(feature = "today")]
-use advent_of_code::template::Day;
+use advent_of_code::template::{Day, Puzzle};
 #[cfg(feature = "today")]
 use std::process;
 
+/// Solutions linked into this binary, generated by `build.rs`.
+#[cfg(not(feature = "dhat-heap"))]
+mod registry {
+    include!(concat!(env!("OUT_DIR"), "/registry.rs"));
+}
+
+/// Every solution declares its own global allocator when profiling with DHAT,
+/// so they can not be linked into one binary. Solutions are spawned instead.
+#[cfg(feature = "dhat-heap")]
+const SOLUTIONS: &[Solution] = &[];
+
 mod args {
-    use advent_of_code::template::Day;
+    use advent_of_code::template::{Day, Puzzle, Visualization, Year};
     use std::process;
 
     pub enum AppArguments {
         Download {
-            day: Day,
+            puzzle: Puzzle,
+            offline: bool,
         },
         Read {
-            day: Day,
+            puzzle: Puzzle,
         },
         Scaffold {
-            day: Day,
+            puzzle: Puzzle,
             download: bool,
             overwrite: bool,
+            offline: bool,
         },
         Solve {
-            day: Day,
+            puzzle: Puzzle,
             release: bool,
             dhat: bool,
+            variants: bool,
+            scaling: bool,
+            differential: bool,
+            visualize: Option<Visualization>,
+            size: Option<usize>,
+            runs: Option<usize>,
             submit: Option<u8>,
         },
+        Generate {
+            puzzle: Puzzle,
+            size: usize,
+            seed: u64,
+        },
         All {
+            year: Year,
             release: bool,
+            jobs: usize,
+            verify: bool,
         },
         Time {
+            year: Year,
             all: bool,
             day: Option<Day>,
             store: bool,
+            jobs: usize,
+            dhat: bool,
+            compare: Option<String>,
+            threshold: f64,
         },
         #[cfg(feature = "today")]
-        Today,
+        Today {
+            year: Year,
+        },
     }
 
     pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
         let mut args = pico_args::Arguments::from_env();
 
-        let app_args = match args.subcommand()?.as_deref() {
+        let subcommand = args.subcommand()?;
+        // NOTE: options need to be parsed before free-standing arguments.
+        let year = parse_year(&mut args)?;
+
+        let app_args = match subcommand.as_deref() {
             Some("all") => AppArguments::All {
+                year,
                 release: args.contains("--release"),
+                jobs: parse_jobs(&mut args)?,
+                verify: args.contains("--verify"),
             },
             Some("time") => {
                 let all = args.contains("--all");
                 let store = args.contains("--store");
+                let jobs = parse_jobs(&mut args)?;
+                let dhat = args.contains("--dhat");
+                let compare = args.opt_value_from_str("--compare")?;
+                let threshold = parse_threshold(&mut args)?;
+                let day = match args.opt_free_from_str()? {
+                    Some(day) => Some(parse_day(year, day)?),
+                    None => None,
+                };
 
                 AppArguments::Time {
+                    year,
                     all,
-                    day: args.opt_free_from_str()?,
+                    day,
                     store,
+                    jobs,
+                    dhat,
+                    compare,
+                    threshold,
                 }
             }
             Some("download") => AppArguments::Download {
-                day: args.free_from_str()?,
+                puzzle: parse_puzzle(year, &mut args)?,
+                offline: args.contains("--offline"),
             },
             Some("read") => AppArguments::Read {
-                day: args.free_from_str()?,
+                puzzle: parse_puzzle(year, &mut args)?,
             },
             Some("scaffold") => AppArguments::Scaffold {
-                day: args.free_from_str()?,
+                puzzle: parse_puzzle(year, &mut args)?,
                 download: args.contains("--download"),
                 overwrite: args.contains("--overwrite"),
+                offline: args.contains("--offline"),
             },
             Some("solve") => AppArguments::Solve {
-                day: args.free_from_str()?,
+                puzzle: parse_puzzle(year, &mut args)?,
                 release: args.contains("--release"),
                 submit: args.opt_value_from_str("--submit")?,
                 dhat: args.contains("--dhat"),
+                variants: args.contains("--variants"),
+                scaling: args.contains("--scaling"),
+                differential: args.contains("--differential"),
+                visualize: args
+                    .contains("--visualize")
+                    .then_some(if args.contains("--svg") {
+                        Visualization::Svg
+                    } else {
+                        Visualization::Text
+                    }),
+                size: args.opt_value_from_str("--size")?,
+                runs: args.opt_value_from_str("--runs")?,
             },
+            Some("generate") => {
+                let size = args.opt_value_from_str("--size")?.unwrap_or(1000);
+                let seed = args.opt_value_from_str("--seed")?.unwrap_or(1);
+
+                AppArguments::Generate {
+                    puzzle: parse_puzzle(year, &mut args)?,
+                    size,
+                    seed,
+                }
+            }
             #[cfg(feature = "today")]
-            Some("today") => AppArguments::Today,
+            Some("today") => AppArguments::Today { year },
             Some(x) => {
                 eprintln!("Unknown command: {x}");
                 process::exit(1);
@@ -93,6 +176,59 @@ mod args {
 
         Ok(app_args)
     }
+
+    /// Parse the `--year <year>` option, defaulting to the `AOC_YEAR` environment variable.
+    fn parse_year(args: &mut pico_args::Arguments) -> Result<Year, Box<dyn std::error::Error>> {
+        match args.opt_value_from_str("--year")? {
+            Some(year) => Ok(year),
+            None => Year::from_env()
+                .ok_or_else(|| "pass `--year <year>` or set `AOC_YEAR` to a valid year.".into()),
+        }
+    }
+
+    /// Parse a day number as a puzzle of a year.
+    fn parse_puzzle(
+        year: Year,
+        args: &mut pico_args::Arguments,
+    ) -> Result<Puzzle, Box<dyn std::error::Error>> {
+        Ok(Puzzle::new(year, parse_day(year, args.free_from_str()?)?))
+    }
+
+    /// Check that a year has a puzzle on a day.
+    fn parse_day(year: Year, day: Day) -> Result<Day, Box<dyn std::error::Error>> {
+        if year.has_day(day) {
+            Ok(day)
+        } else {
+            Err(format!(
+                "{year} only has {} puzzles, expecting a day number between 1 and {}.",
+                year.day_count(),
+                year.day_count()
+            )
+            .into())
+        }
+    }
+
+    /// Parse the `--threshold <percent>` option of `--compare`, defaulting to 10%.
+    fn parse_threshold(args: &mut pico_args::Arguments) -> Result<f64, Box<dyn std::error::Error>> {
+        let threshold: f64 = args.opt_value_from_str("--threshold")?.unwrap_or(10.0);
+
+        if !threshold.is_finite() || threshold < 0.0 {
+            return Err("`--threshold` expects a percentage of at least 0.".into());
+        }
+
+        Ok(threshold)
+    }
+
+    /// Parse the `--jobs <n>` option, defaulting to running one day at a time.
+    fn parse_jobs(args: &mut pico_args::Arguments) -> Result<usize, Box<dyn std::error::Error>> {
+        let jobs = args.opt_value_from_str("--jobs")?.unwrap_or(1);
+
+        if jobs == 0 {
+            return Err("`--jobs` expects a number greater than 0.".into());
+        }
+
+        Ok(jobs)
+    }
 }
 
 fn main() {
@@ -102,38 +238,82 @@ fn main() {
             std::process::exit(1);
         }
         Ok(args) => match args {
-            AppArguments::All { release } => all::handle(release),
-            AppArguments::Time { day, all, store } => time::handle(day, all, store),
-            AppArguments::Download { day } => download::handle(day),
-            AppArguments::Read { day } => read::handle(day),
-            AppArguments::Scaffold {
+            AppArguments::All {
+                year,
+                release,
+                jobs,
+                verify,
+            } => all::handle(year, release, jobs, verify, SOLUTIONS),
+            AppArguments::Time {
+                year,
                 day,
+                all,
+                store,
+                jobs,
+                dhat,
+                compare,
+                threshold,
+            } => time::handle(
+                year, day, all, store, jobs, dhat, compare, threshold, SOLUTIONS,
+            ),
+            AppArguments::Download { puzzle, offline } => download::handle(puzzle, offline),
+            AppArguments::Read { puzzle } => read::handle(puzzle),
+            AppArguments::Scaffold {
+                puzzle,
                 download,
                 overwrite,
+                offline,
             } => {
-                scaffold::handle(day, overwrite);
+                scaffold::handle(puzzle, overwrite);
                 if download {
-                    download::handle(day);
+                    download::handle(puzzle, offline);
                 }
             }
             AppArguments::Solve {
-                day,
+                puzzle,
                 release,
                 dhat,
+                variants,
+                scaling,
+                differential,
+                visualize,
+                size,
+                runs,
                 submit,
-            } => solve::handle(day, release, dhat, submit),
+            } => {
+                if let Err(e) = solve::handle(
+                    puzzle,
+                    release,
+                    dhat,
+                    variants,
+                    scaling,
+                    differential,
+                    visualize,
+                    size,
+                    runs,
+                    submit,
+                ) {
+                    eprintln!("failed to run cargo: {e}");
+                    std::process::exit(1);
+                }
+            }
+            AppArguments::Generate { puzzle, size, seed } => {
+                generate::handle(puzzle, size, seed, SOLUTIONS);
+            }
             #[cfg(feature = "today")]
-            AppArguments::Today => {
-                match Day::today() {
+            AppArguments::Today { year } => {
+                match Day::today(year) {
                     Some(day) => {
-                        scaffold::handle(day, false);
-                        download::handle(day);
-                        read::handle(day)
+                        let puzzle = Puzzle::new(year, day);
+                        scaffold::handle(puzzle, false);
+                        download::handle(puzzle, false);
+                        read::handle(puzzle)
                     }
                     None => {
                         eprintln!(
-                            "`today` command can only be run between the 1st and \
-                            the 25th of december. Please use `scaffold` with a specific day."
+                            "`today` command can only be run between the 1st and the {}th \
+                            of december in {year}. Please use `scaffold` with a specific day.",
+                            year.day_count()
                         );
                         process::exit(1)
                     }
@@ -0,0 +1,299 @@
+/// Module that keeps track of known-good answers, so that refactors can be verified against them.
+use std::{
+    collections::HashMap,
+    fs,
+    io::{Error, ErrorKind},
+    str::FromStr,
+};
+use tinyjson::JsonValue;
+
+use crate::template::runner::PartResult;
+use crate::template::{Day, Year};
+
+/// Represents the accepted answers for a single day.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Answer {
+    pub day: Day,
+    pub part_1: Option<String>,
+    pub part_2: Option<String>,
+}
+
+/// Represents the accepted answers for a set of days.
+/// Can be serialized from / to JSON.
+#[derive(Clone, Debug, Default)]
+pub struct Answers {
+    pub data: Vec<Answer>,
+}
+
+/// The outcome of comparing a part result against its known answer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Verification {
+    /// The result matches the known answer.
+    Match,
+    /// The result differs from the known answer, which is included.
+    Mismatch(String),
+    /// There is no known answer for this part.
+    Unknown,
+}
+
+impl Answers {
+    /// Dehydrate the answers of a year to a JSON file.
+    pub fn store_file(&self, year: Year) -> Result<(), Error> {
+        fs::create_dir_all(format!("./data/{year}"))?;
+        let json = JsonValue::from(self.clone());
+        let mut file = fs::File::create(get_path(year))?;
+        json.format_to(&mut file)
+    }
+
+    /// Rehydrate the answers of a year from a JSON file. If not present, returns empty answers.
+    /// Fails if the file can not be read or parsed, so that known answers are never overwritten.
+    pub fn read_from_file(year: Year) -> Result<Self, String> {
+        let path = get_path(year);
+
+        match fs::read_to_string(&path) {
+            Ok(contents) => Answers::try_from(contents).map_err(|e| format!("\"{path}\": {e}")),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(Answers::default()),
+            Err(e) => Err(format!("\"{path}\": {e}")),
+        }
+    }
+
+    /// Get the known answer for a part of a day.
+    pub fn get(&self, day: Day, part: u8) -> Option<&str> {
+        let answer = self.data.iter().find(|a| a.day == day)?;
+
+        match part {
+            1 => answer.part_1.as_deref(),
+            2 => answer.part_2.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// Record the answer for a part of a day, overwriting a previously known answer.
+    pub fn set(&mut self, day: Day, part: u8, value: &str) {
+        let index = match self.data.iter().position(|a| a.day == day) {
+            Some(index) => index,
+            None => {
+                self.data.push(Answer {
+                    day,
+                    part_1: None,
+                    part_2: None,
+                });
+                self.data.sort_unstable_by_key(|a| a.day);
+                self.data.iter().position(|a| a.day == day).unwrap()
+            }
+        };
+
+        let answer = &mut self.data[index];
+
+        match part {
+            1 => answer.part_1 = Some(value.into()),
+            2 => answer.part_2 = Some(value.into()),
+            _ => {}
+        }
+    }
+
+    /// Whether any answer is known for a day.
+    pub fn has_day(&self, day: Day) -> bool {
+        self.data
+            .iter()
+            .any(|a| a.day == day && (a.part_1.is_some() || a.part_2.is_some()))
+    }
+
+    /// Compare a part result against the known answer.
+    pub fn verify(&self, result: &PartResult) -> Verification {
+        match self.get(result.day, result.part) {
+            None => Verification::Unknown,
+            Some(expected) if result.answer.as_deref() == Some(expected) => Verification::Match,
+            Some(expected) => Verification::Mismatch(expected.into()),
+        }
+    }
+}
+
+fn get_path(year: Year) -> String {
+    format!("./data/{year}/answers.json")
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<Answers> for JsonValue {
+    fn from(value: Answers) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for Answers {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json_data = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("data")
+            .ok_or("expected JSON document to have key `data`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.data` to be an array.")?;
+
+        Ok(Answers {
+            data: json_data
+                .iter()
+                .map(Answer::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Answer> for JsonValue {
+    fn from(value: &Answer) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+
+        map.insert(
+            "part_1".into(),
+            value
+                .part_1
+                .clone()
+                .map_or(JsonValue::Null, JsonValue::String),
+        );
+
+        map.insert(
+            "part_2".into(),
+            value
+                .part_2
+                .clone()
+                .map_or(JsonValue::Null, JsonValue::String),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Answer {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected answer to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected answer.day to be a Day struct.")?;
+
+        let part_1 = json
+            .get("part_1")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected answer.part_1 to be null or string.")?;
+
+        let part_2 = json
+            .get("part_2")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected answer.part_2 to be null or string.")?;
+
+        Ok(Answer {
+            day,
+            part_1: part_1.cloned(),
+            part_2: part_2.cloned(),
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use crate::{day, template::runner::PartResult};
+
+    use super::{Answers, Verification};
+
+    fn get_mock_answers() -> Answers {
+        let json = r#"{ "data": [{ "day": "01", "part_1": "3", "part_2": null }, { "day": "08", "part_1": "40", "part_2": "25272" }] }"#;
+        Answers::try_from(json.to_string()).unwrap()
+    }
+
+    fn get_mock_result(day: u8, part: u8, answer: Option<&str>) -> PartResult {
+        PartResult {
+            day: crate::template::Day::new(day).unwrap(),
+            part,
+            answer: answer.map(Into::into),
+            duration_nanos: 0.0,
+            samples: 1,
+            statistics: None,
+            heap: None,
+        }
+    }
+
+    #[test]
+    fn handles_json_answers() {
+        let answers = get_mock_answers();
+        assert_eq!(answers.data.len(), 2);
+        assert_eq!(answers.get(day!(1), 1), Some("3"));
+        assert_eq!(answers.get(day!(1), 2), None);
+        assert_eq!(answers.get(day!(8), 2), Some("25272"));
+        assert_eq!(answers.get(day!(2), 1), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_malformed_answers() {
+        let json = r#"{ "data": [{ "day": "26", "part_1": "1", "part_2": null }] }"#;
+        Answers::try_from(json.to_string()).unwrap();
+    }
+
+    #[test]
+    fn sets_answers() {
+        let mut answers = get_mock_answers();
+        answers.set(day!(1), 2, "6");
+        answers.set(day!(4), 1, "13");
+        answers.set(day!(8), 1, "41");
+
+        assert_eq!(answers.data.len(), 3);
+        assert_eq!(answers.data[1].day, day!(4));
+        assert_eq!(answers.get(day!(1), 2), Some("6"));
+        assert_eq!(answers.get(day!(4), 1), Some("13"));
+        assert_eq!(answers.get(day!(8), 1), Some("41"));
+    }
+
+    #[test]
+    fn roundtrips_json() {
+        let answers = get_mock_answers();
+        let json = tinyjson::JsonValue::from(answers.clone());
+        let reparsed = Answers::try_from(json.stringify().unwrap()).unwrap();
+        assert_eq!(reparsed.data, answers.data);
+    }
+
+    #[test]
+    fn verifies_results() {
+        let answers = get_mock_answers();
+
+        assert_eq!(
+            answers.verify(&get_mock_result(1, 1, Some("3"))),
+            Verification::Match
+        );
+        assert_eq!(
+            answers.verify(&get_mock_result(1, 1, Some("4"))),
+            Verification::Mismatch("3".into())
+        );
+        assert_eq!(
+            answers.verify(&get_mock_result(8, 2, None)),
+            Verification::Mismatch("25272".into())
+        );
+        assert_eq!(
+            answers.verify(&get_mock_result(1, 2, Some("6"))),
+            Verification::Unknown
+        );
+    }
+}
@@ -0,0 +1,618 @@
+/// Module that talks to the Advent of Code website: downloads inputs and puzzles and submits answers.
+use std::{
+    env,
+    fmt::Display,
+    fs,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use ureq::{Agent, Body, http::Response};
+
+use crate::template::Puzzle;
+use crate::template::input_cache::InputCache;
+use crate::template::submissions::SubmitOutcome;
+
+static BASE_URL: &str = "https://adventofcode.com";
+static USER_AGENT: &str = "github.com/fspoettel/advent-of-code-rust";
+
+/// Environment variable that contains the session cookie.
+static SESSION_ENV: &str = "AOC_SESSION";
+/// Environment variable that points to a file containing the session cookie.
+/// Defaults to `<home_directory>/.adventofcode.session`.
+static SESSION_FILE_ENV: &str = "AOC_SESSION_FILE";
+
+#[derive(Debug)]
+pub enum AocClientError {
+    SessionNotFound,
+    Request(ureq::Error),
+    BadStatus { url: String, status: u16 },
+    Io(std::io::Error),
+    NotCached(Puzzle),
+}
+
+impl Display for AocClientError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AocClientError::SessionNotFound => write!(
+                f,
+                "no session cookie found. Set {SESSION_ENV} or store it in \"~/.adventofcode.session\"."
+            ),
+            AocClientError::Request(e) => write!(f, "request failed: {e}"),
+            AocClientError::BadStatus { url, status } => {
+                write!(f, "request to {url} failed with status {status}.")
+            }
+            AocClientError::Io(e) => write!(f, "could not write file: {e}"),
+            AocClientError::NotCached(puzzle) => write!(
+                f,
+                "no valid cached input for day {} of {}, it can not be downloaded with `--offline`.",
+                puzzle.day, puzzle.year
+            ),
+        }
+    }
+}
+
+impl From<ureq::Error> for AocClientError {
+    fn from(value: ureq::Error) -> Self {
+        AocClientError::Request(value)
+    }
+}
+
+impl From<std::io::Error> for AocClientError {
+    fn from(value: std::io::Error) -> Self {
+        AocClientError::Io(value)
+    }
+}
+
+/// An authenticated client for the Advent of Code website.
+pub struct AocClient {
+    agent: Agent,
+    base_url: String,
+    session: String,
+}
+
+impl AocClient {
+    pub fn new(base_url: &str, session: &str) -> Self {
+        let agent = Agent::config_builder()
+            .user_agent(USER_AGENT)
+            .http_status_as_error(false)
+            .timeout_global(Some(Duration::from_secs(30)))
+            .build()
+            .into();
+
+        AocClient {
+            agent,
+            base_url: base_url.trim_end_matches('/').into(),
+            session: session.into(),
+        }
+    }
+
+    /// Create a client for adventofcode.com, using the session cookie of the environment.
+    pub fn from_env() -> Result<Self, AocClientError> {
+        let session = read_session().ok_or(AocClientError::SessionNotFound)?;
+        Ok(AocClient::new(BASE_URL, &session))
+    }
+
+    /// Fetch the input of a puzzle.
+    pub fn input(&self, puzzle: Puzzle) -> Result<String, AocClientError> {
+        let url = format!("{}/input", self.puzzle_url(puzzle));
+        let response = self
+            .agent
+            .get(&url)
+            .header("Cookie", &self.cookie())
+            .call()?;
+        read_body(url, response)
+    }
+
+    /// Fetch the description of a puzzle, converted to markdown.
+    pub fn puzzle(&self, puzzle: Puzzle) -> Result<String, AocClientError> {
+        let url = self.puzzle_url(puzzle);
+        let response = self
+            .agent
+            .get(&url)
+            .header("Cookie", &self.cookie())
+            .call()?;
+        Ok(html_to_markdown(&read_body(url, response)?))
+    }
+
+    /// Submit an answer and return the message of the server, converted to markdown.
+    pub fn submit(&self, puzzle: Puzzle, part: u8, answer: &str) -> Result<String, AocClientError> {
+        let url = format!("{}/answer", self.puzzle_url(puzzle));
+        let response = self
+            .agent
+            .post(&url)
+            .header("Cookie", &self.cookie())
+            .send_form([("level", part.to_string().as_str()), ("answer", answer)])?;
+        Ok(html_to_markdown(&read_body(url, response)?))
+    }
+
+    fn puzzle_url(&self, puzzle: Puzzle) -> String {
+        format!(
+            "{}/{}/day/{}",
+            self.base_url,
+            puzzle.year,
+            puzzle.day.into_inner()
+        )
+    }
+
+    fn cookie(&self) -> String {
+        format!("session={}", self.session)
+    }
+}
+
+fn read_body(url: String, mut response: Response<Body>) -> Result<String, AocClientError> {
+    let status = response.status().as_u16();
+
+    if status != 200 {
+        return Err(AocClientError::BadStatus { url, status });
+    }
+
+    Ok(response.body_mut().read_to_string()?)
+}
+
+/* -------------------------------------------------------------------------- */
+
+pub fn read(puzzle: Puzzle) -> Result<(), AocClientError> {
+    let description = AocClient::from_env()?.puzzle(puzzle)?;
+    write_file(&puzzle.data_path("puzzles", "md"), &description)?;
+    println!("{description}");
+    Ok(())
+}
+
+/// Download the input and description of a puzzle. An input that was downloaded before and is unchanged is not fetched again.
+/// In `offline` mode, no requests are made and an error is returned if the input is not cached.
+pub fn download(puzzle: Puzzle, offline: bool) -> Result<(), AocClientError> {
+    let Puzzle { year, day } = puzzle;
+    let input_path = puzzle.data_path("inputs", "txt");
+    let puzzle_path = puzzle.data_path("puzzles", "md");
+
+    // NOTE: a cache that can not be parsed is never overwritten, inputs are downloaded again instead.
+    let mut cache = InputCache::read_from_file(year)
+        .map_err(|e| eprintln!("Warning: could not read input metadata from {e}"))
+        .ok();
+    let is_cached = fs::read_to_string(&input_path)
+        .is_ok_and(|x| cache.as_ref().is_some_and(|c| c.is_valid(day, year, &x)));
+
+    if offline {
+        if !is_cached {
+            return Err(AocClientError::NotCached(puzzle));
+        }
+
+        println!("🎄 Using cached input \"{}\".", &input_path);
+        return Ok(());
+    }
+
+    let client = AocClient::from_env()?;
+
+    if is_cached {
+        println!("🎄 Using cached input \"{}\".", &input_path);
+    } else {
+        let input = client.input(puzzle)?;
+        write_file(&input_path, &input)?;
+        if let Some(cache) = &mut cache {
+            cache.insert(day, year, &input);
+            cache.store_file(year)?;
+        }
+        println!("🎄 Successfully wrote input to \"{}\".", &input_path);
+    }
+
+    write_file(&puzzle_path, &client.puzzle(puzzle)?)?;
+    println!("🎄 Successfully wrote puzzle to \"{}\".", &puzzle_path);
+    Ok(())
+}
+
+/// Submit an answer. The response of the server is printed and interpreted as a [`SubmitOutcome`].
+pub fn submit(puzzle: Puzzle, part: u8, result: &str) -> Result<SubmitOutcome, AocClientError> {
+    let message = AocClient::from_env()?.submit(puzzle, part, result)?;
+    println!("{message}");
+    Ok(SubmitOutcome::from_response(&message))
+}
+
+/// Write a file, creating its directory if needed.
+fn write_file(path: &str, contents: &str) -> Result<(), AocClientError> {
+    if let Some(dir) = Path::new(path).parent() {
+        fs::create_dir_all(dir)?;
+    }
+    Ok(fs::write(path, contents)?)
+}
+
+fn read_session() -> Option<String> {
+    let session = match env::var(SESSION_ENV) {
+        Ok(x) => x,
+        Err(_) => {
+            let path = match env::var_os(SESSION_FILE_ENV) {
+                Some(x) => PathBuf::from(x),
+                None => PathBuf::from(env::var_os("HOME").or_else(|| env::var_os("USERPROFILE"))?)
+                    .join(".adventofcode.session"),
+            };
+            fs::read_to_string(path).ok()?
+        }
+    };
+
+    let session = session.trim();
+    (!session.is_empty()).then(|| session.into())
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Convert the `<article>` elements of a page to markdown.
+/// Only handles the subset of HTML that is used in puzzle descriptions and answer responses.
+fn html_to_markdown(html: &str) -> String {
+    let mut out = String::new();
+    let mut rest = html;
+
+    while let Some(start) = rest.find("<article") {
+        let article = &rest[start..];
+        let end = article.find("</article>").unwrap_or(article.len());
+        let body = article[..end].split_once('>').map_or("", |(_, body)| body);
+
+        convert_fragment(body, &mut out);
+        end_block(&mut out);
+
+        rest = &article[end..];
+    }
+
+    let out = out.trim_end();
+    if out.is_empty() {
+        String::new()
+    } else {
+        format!("{out}\n")
+    }
+}
+
+fn convert_fragment(html: &str, out: &mut String) {
+    let mut rest = html;
+    let mut in_pre = false;
+    let mut links: Vec<String> = vec![];
+
+    while !rest.is_empty() {
+        let Some(tag_start) = rest.find('<') else {
+            push_text(rest, in_pre, out);
+            break;
+        };
+
+        push_text(&rest[..tag_start], in_pre, out);
+        rest = &rest[tag_start..];
+
+        if let Some(comment) = rest.strip_prefix("<!--") {
+            rest = comment.split_once("-->").map_or("", |(_, x)| x);
+            continue;
+        }
+
+        let Some(tag_end) = rest.find('>') else {
+            break;
+        };
+
+        let tag = &rest[1..tag_end];
+        rest = &rest[tag_end + 1..];
+
+        let (is_closing, tag) = match tag.strip_prefix('/') {
+            Some(tag) => (true, tag),
+            None => (false, tag),
+        };
+
+        let name = tag
+            .split(|c: char| c.is_whitespace() || c == '/')
+            .next()
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+
+        match (name.as_str(), is_closing) {
+            ("h2", false) => {
+                end_block(out);
+                out.push_str("## ");
+            }
+            ("p" | "h2" | "ul", _) => end_block(out),
+            ("pre", false) => {
+                end_block(out);
+                out.push_str("```\n");
+                in_pre = true;
+            }
+            ("pre", true) => {
+                if !out.ends_with('\n') {
+                    out.push('\n');
+                }
+                out.push_str("```");
+                end_block(out);
+                in_pre = false;
+            }
+            ("code", _) if !in_pre => out.push('`'),
+            ("em", _) if !in_pre => out.push('*'),
+            ("li", false) => {
+                trim_trailing_spaces(out);
+                if !out.is_empty() && !out.ends_with('\n') {
+                    out.push('\n');
+                }
+                out.push_str("- ");
+            }
+            ("li", true) => {
+                trim_trailing_spaces(out);
+                out.push('\n');
+            }
+            ("a", false) => {
+                links.push(get_attribute(tag, "href").unwrap_or_default());
+                out.push('[');
+            }
+            ("a", true) => {
+                let href = links.pop().unwrap_or_default();
+                out.push_str(&format!("]({href})"));
+            }
+            ("br", _) => out.push('\n'),
+            _ => {}
+        }
+    }
+}
+
+/// Append text, collapsing whitespace outside of preformatted blocks.
+fn push_text(text: &str, in_pre: bool, out: &mut String) {
+    let text = decode_entities(text);
+
+    if in_pre {
+        out.push_str(&text);
+        return;
+    }
+
+    for (i, word) in text.split_whitespace().enumerate() {
+        let at_line_start = out.is_empty() || out.ends_with(['\n', ' ']);
+
+        if (i > 0 || text.starts_with(char::is_whitespace)) && !at_line_start {
+            out.push(' ');
+        }
+
+        out.push_str(word);
+    }
+
+    if text.ends_with(char::is_whitespace) && !text.trim().is_empty() {
+        out.push(' ');
+    }
+}
+
+/// Terminate the current block with an empty line.
+fn end_block(out: &mut String) {
+    trim_trailing_spaces(out);
+
+    if !out.is_empty() {
+        while !out.ends_with("\n\n") {
+            out.push('\n');
+        }
+    }
+}
+
+fn trim_trailing_spaces(out: &mut String) {
+    out.truncate(out.trim_end_matches(' ').len());
+}
+
+fn get_attribute(tag: &str, name: &str) -> Option<String> {
+    let (_, rest) = tag.split_once(&format!("{name}=\""))?;
+    let (value, _) = rest.split_once('"')?;
+    Some(decode_entities(value))
+}
+
+fn decode_entities(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(start) = rest.find('&') {
+        out.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let decoded = rest.find(';').and_then(|end| {
+            let entity = &rest[1..end];
+
+            let c = match entity {
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "amp" => Some('&'),
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                "nbsp" => Some(' '),
+                _ => match entity.strip_prefix('#') {
+                    Some(hex) if hex.starts_with(['x', 'X']) => u32::from_str_radix(&hex[1..], 16)
+                        .ok()
+                        .and_then(char::from_u32),
+                    Some(dec) => dec.parse().ok().and_then(char::from_u32),
+                    None => None,
+                },
+            };
+
+            c.map(|c| (c, end))
+        });
+
+        match decoded {
+            Some((c, end)) => {
+                out.push(c);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+
+    out.push_str(rest);
+    out
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        thread::{self, JoinHandle},
+    };
+
+    use crate::template::{Puzzle, submissions::SubmitOutcome};
+    use crate::{day, year};
+
+    use super::{AocClient, AocClientError, html_to_markdown};
+
+    /// Serve one canned response per connection and return the raw requests that were received.
+    fn serve(responses: Vec<(u16, &'static str)>) -> (String, JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+
+        let handle = thread::spawn(move || {
+            let mut requests = vec![];
+
+            for (status, body) in responses {
+                let (stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream);
+                let mut request = String::new();
+                let mut content_length = 0;
+
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+
+                    if let Some((name, value)) = line.split_once(':')
+                        && name.eq_ignore_ascii_case("content-length")
+                    {
+                        content_length = value.trim().parse().unwrap();
+                    }
+
+                    request.push_str(&line);
+
+                    if line == "\r\n" || line.is_empty() {
+                        break;
+                    }
+                }
+
+                let mut body_bytes = vec![0; content_length];
+                reader.read_exact(&mut body_bytes).unwrap();
+                request.push_str(&String::from_utf8(body_bytes).unwrap());
+                requests.push(request);
+
+                let response = format!(
+                    "HTTP/1.1 {status} Status\r\nContent-Type: text/html\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                    body.len()
+                );
+                reader.get_mut().write_all(response.as_bytes()).unwrap();
+            }
+
+            requests
+        });
+
+        (url, handle)
+    }
+
+    fn has_header(request: &str, name: &str, value: &str) -> bool {
+        request.lines().any(|line| {
+            line.split_once(':')
+                .is_some_and(|(n, v)| n.eq_ignore_ascii_case(name) && v.trim() == value)
+        })
+    }
+
+    #[test]
+    fn downloads_inputs() {
+        let (url, server) = serve(vec![(200, "1\n2\n3\n")]);
+        let client = AocClient::new(&url, "abc");
+
+        assert_eq!(
+            client.input(Puzzle::new(year!(2025), day!(1))).unwrap(),
+            "1\n2\n3\n"
+        );
+
+        let requests = server.join().unwrap();
+        assert!(requests[0].starts_with("GET /2025/day/1/input HTTP/1.1\r\n"));
+        assert!(has_header(&requests[0], "cookie", "session=abc"));
+        assert!(has_header(
+            &requests[0],
+            "user-agent",
+            "github.com/fspoettel/advent-of-code-rust"
+        ));
+    }
+
+    #[test]
+    fn downloads_puzzles() {
+        let page = "<html><body><main><article class=\"day-desc\"><h2>--- Day 12: Test ---</h2><p>Hello <em>world</em>.</p></article><p>Your puzzle answer was <code>42</code>.</p></main></body></html>";
+        let (url, server) = serve(vec![(200, page)]);
+        let client = AocClient::new(&url, "abc");
+
+        assert_eq!(
+            client.puzzle(Puzzle::new(year!(2025), day!(12))).unwrap(),
+            "## --- Day 12: Test ---\n\nHello *world*.\n"
+        );
+
+        let requests = server.join().unwrap();
+        assert!(requests[0].starts_with("GET /2025/day/12 HTTP/1.1\r\n"));
+    }
+
+    #[test]
+    fn submits_answers() {
+        let page = "<main><article><p>That's not the right answer; your answer is too high.  If you're stuck, make sure you're using the full input data. <a href=\"/2025/day/3\">[Return to Day 3]</a></p></article></main>";
+        let (url, server) = serve(vec![(200, page)]);
+        let client = AocClient::new(&url, "abc");
+
+        let message = client
+            .submit(Puzzle::new(year!(2025), day!(3)), 2, "42")
+            .unwrap();
+        assert_eq!(
+            message,
+            "That's not the right answer; your answer is too high. If you're stuck, make sure you're using the full input data. [[Return to Day 3]](/2025/day/3)\n"
+        );
+        assert_eq!(
+            SubmitOutcome::from_response(&message),
+            SubmitOutcome::TooHigh
+        );
+
+        let requests = server.join().unwrap();
+        assert!(requests[0].starts_with("POST /2025/day/3/answer HTTP/1.1\r\n"));
+        assert!(has_header(&requests[0], "cookie", "session=abc"));
+        assert!(requests[0].ends_with("\r\n\r\nlevel=2&answer=42"));
+    }
+
+    #[test]
+    fn fails_for_bad_status() {
+        let (url, server) = serve(vec![(400, "Puzzle inputs differ by user.")]);
+        let client = AocClient::new(&url, "abc");
+
+        assert!(matches!(
+            client.input(Puzzle::new(year!(2025), day!(1))),
+            Err(AocClientError::BadStatus { status: 400, .. })
+        ));
+
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn converts_html_to_markdown() {
+        let html = r#"<article class="day-desc"><h2>--- Day 1: Test ---</h2>
+<p>The <em>first</em> line &amp; <code>a &lt; b</code>.
+  Continued <a href="https://example.com" target="_blank">here</a>.</p>
+<!-- comment -->
+<pre><code>L68
+<em>L30</em>
+</code></pre>
+<ul>
+<li>One <code>1</code></li>
+<li>Two</li>
+</ul>
+<p>It&apos;s <span title="hidden">&#x2603;</span>&#33;</p>
+</article>
+<article><h2 id="part2">--- Part Two ---</h2><p>More.</p></article>"#;
+
+        let expected = "## --- Day 1: Test ---
+
+The *first* line & `a < b`. Continued [here](https://example.com).
+
+```
+L68
+L30
+```
+
+- One `1`
+- Two
+
+It's ☃!
+
+## --- Part Two ---
+
+More.
+";
+
+        assert_eq!(html_to_markdown(html), expected);
+    }
+}
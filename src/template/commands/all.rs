@@ -1,5 +1,38 @@
-use crate::template::{all_days, run_multi::run_multi};
+use std::process;
 
-pub fn handle(is_release: bool) {
-    run_multi(&all_days().collect(), is_release, false);
+use crate::template::{
+    Solution, Year, all_days,
+    answers::Answers,
+    run_multi::{Profile, run_multi},
+};
+
+pub fn handle(year: Year, is_release: bool, jobs: usize, verify: bool, solutions: &[Solution]) {
+    let answers = verify.then(|| {
+        Answers::read_from_file(year).unwrap_or_else(|e| {
+            eprintln!("Error: could not read known answers from {e}");
+            process::exit(1);
+        })
+    });
+
+    let summary = run_multi(
+        year,
+        &all_days(year).collect(),
+        if is_release {
+            Profile::Release
+        } else {
+            Profile::Debug
+        },
+        false,
+        jobs,
+        solutions,
+        answers.as_ref(),
+    );
+
+    if summary.failed_verifications > 0 {
+        eprintln!(
+            "\n{} part(s) did not match their known answer.",
+            summary.failed_verifications
+        );
+        process::exit(1);
+    }
 }
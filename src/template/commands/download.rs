@@ -1,16 +1,10 @@
-use crate::template::{Day, aoc_cli};
 use std::process;
 
-pub fn handle(day: Day) {
-    if aoc_cli::check().is_err() {
-        eprintln!(
-            "command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it."
-        );
-        process::exit(1);
-    }
+use crate::template::{Puzzle, aoc_client};
 
-    if let Err(e) = aoc_cli::download(day) {
-        eprintln!("failed to call aoc-cli: {e}");
+pub fn handle(puzzle: Puzzle, offline: bool) {
+    if let Err(e) = aoc_client::download(puzzle, offline) {
+        eprintln!("failed to download puzzle: {e}");
         process::exit(1);
     };
 }
@@ -0,0 +1,24 @@
+use std::{
+    io::{Write, stdout},
+    process,
+};
+
+use crate::template::{Puzzle, Solution};
+
+/// Print a generated input of `size` records for a puzzle, see [`crate::template::Generator`].
+pub fn handle(puzzle: Puzzle, size: usize, seed: u64, solutions: &[Solution]) {
+    let Some(solution) = solutions.iter().find(|s| s.puzzle == puzzle) else {
+        eprintln!("No solution found for {puzzle}. Run `cargo scaffold` first.");
+        process::exit(1);
+    };
+
+    let Some(generator) = solution.generator else {
+        eprintln!(
+            "{puzzle} has no input generator. Register one in its `solution!` macro, e.g. `generator: generate`."
+        );
+        process::exit(1);
+    };
+
+    // NOTE: ignore broken pipes, e.g. when the output is piped into `head`.
+    let _ = stdout().write_all(generator(size, seed).as_bytes());
+}
@@ -1,5 +1,8 @@
 pub mod all;
+#[cfg(feature = "client")]
 pub mod download;
+pub mod generate;
+#[cfg(feature = "client")]
 pub mod read;
 pub mod scaffold;
 pub mod solve;
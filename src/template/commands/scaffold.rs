@@ -1,15 +1,17 @@
 use std::{
-    fs::{File, OpenOptions},
-    io::Write,
+    fs::{self, File, OpenOptions},
+    io::{self, Write},
+    path::Path,
     process,
 };
 
-use crate::template::Day;
+use crate::template::Puzzle;
 
 const MODULE_TEMPLATE: &str =
     include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/template.txt"));
 
 fn safe_create_file(path: &str, overwrite: bool) -> Result<File, std::io::Error> {
+    create_parent_dir(path)?;
     let mut file = OpenOptions::new();
     if overwrite {
         file.create(true);
@@ -20,6 +22,7 @@ fn safe_create_file(path: &str, overwrite: bool) -> Result<File, std::io::Error>
 }
 
 fn create_file(path: &str) -> Result<File, std::io::Error> {
+    create_parent_dir(path)?;
     OpenOptions::new()
         .write(true)
         .create(true)
@@ -27,10 +30,36 @@ fn create_file(path: &str) -> Result<File, std::io::Error> {
         .open(path)
 }
 
-pub fn handle(day: Day, overwrite: bool) {
-    let input_path = format!("data/inputs/{day}.txt");
-    let example_path = format!("data/examples/{day}.txt");
-    let module_path = format!("src/bin/{day}.rs");
+fn create_parent_dir(path: &str) -> Result<(), std::io::Error> {
+    match Path::new(path).parent() {
+        Some(dir) => fs::create_dir_all(dir),
+        None => Ok(()),
+    }
+}
+
+/// Cargo only discovers binaries directly in `src/bin`, so solutions in year directories are declared in the manifest.
+/// Returns `false` if the binary was declared already.
+fn register_bin(puzzle: Puzzle, module_path: &str) -> Result<bool, io::Error> {
+    let manifest_path = "Cargo.toml";
+    let manifest = fs::read_to_string(manifest_path)?;
+    let name = format!("name = \"{puzzle}\"");
+
+    if manifest.lines().any(|line| line.trim() == name) {
+        return Ok(false);
+    }
+
+    let mut file = OpenOptions::new().append(true).open(manifest_path)?;
+    let separator = if manifest.ends_with('\n') { "" } else { "\n" };
+    let path = module_path.trim_start_matches("./");
+    write!(file, "{separator}\n[[bin]]\n{name}\npath = \"{path}\"\n")?;
+    Ok(true)
+}
+
+pub fn handle(puzzle: Puzzle, overwrite: bool) {
+    let Puzzle { year, day } = puzzle;
+    let input_path = puzzle.data_path("inputs", "txt");
+    let example_path = puzzle.data_path("examples", "txt");
+    let module_path = puzzle.bin_path();
 
     let mut file = match safe_create_file(&module_path, overwrite) {
         Ok(file) => file,
@@ -42,6 +71,7 @@ pub fn handle(day: Day, overwrite: bool) {
 
     match file.write_all(
         MODULE_TEMPLATE
+            .replace("%YEAR%", &year.to_string())
             .replace("%DAY_NUMBER%", &day.into_inner().to_string())
             .as_bytes(),
     ) {
@@ -54,6 +84,17 @@ pub fn handle(day: Day, overwrite: bool) {
         }
     }
 
+    match register_bin(puzzle, &module_path) {
+        Ok(true) => {
+            println!("Registered binary \"{puzzle}\" in \"Cargo.toml\"");
+        }
+        Ok(false) => {}
+        Err(e) => {
+            eprintln!("Failed to register binary in Cargo.toml: {e}");
+            process::exit(1);
+        }
+    }
+
     match create_file(&input_path) {
         Ok(_) => {
             println!("Created empty input file \"{}\"", &input_path);
@@ -75,5 +116,8 @@ pub fn handle(day: Day, overwrite: bool) {
     }
 
     println!("---");
-    println!("🎄 Type `cargo solve {day}` to run your solution.");
+    println!(
+        "🎄 Type `cargo solve {} --year {year}` to run your solution.",
+        day.into_inner()
+    );
 }
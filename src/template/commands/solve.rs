@@ -1,23 +1,72 @@
-use std::process::{Command, Stdio};
+use std::{
+    io,
+    process::{self, Command, Stdio},
+};
 
-use crate::template::Day;
+use crate::template::{Puzzle, Visualization, run_multi::Profile};
 
-pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
-    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
+#[allow(clippy::too_many_arguments)]
+pub fn handle(
+    puzzle: Puzzle,
+    release: bool,
+    dhat: bool,
+    variants: bool,
+    scaling: bool,
+    differential: bool,
+    visualize: Option<Visualization>,
+    size: Option<usize>,
+    runs: Option<usize>,
+    submit_part: Option<u8>,
+) -> io::Result<()> {
+    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), puzzle.to_string()];
 
-    if dhat {
-        cmd_args.extend([
-            "--profile".to_string(),
-            "dhat".to_string(),
-            "--features".to_string(),
-            "dhat-heap".to_string(),
-        ]);
+    let profile = if dhat {
+        Profile::Dhat
     } else if release {
-        cmd_args.push("--release".to_string());
+        Profile::Release
+    } else {
+        Profile::Debug
+    };
+
+    cmd_args.extend(profile.cargo_args().iter().map(ToString::to_string));
+
+    // Only submitting needs the HTTP client.
+    if submit_part.is_some() {
+        cmd_args.push("--features".to_string());
+        cmd_args.push("client".to_string());
     }
 
     cmd_args.push("--".to_string());
 
+    if variants {
+        cmd_args.push("--variants".to_string());
+    }
+
+    if scaling {
+        cmd_args.push("--scaling".to_string());
+    }
+
+    if differential {
+        cmd_args.push("--differential".to_string());
+    }
+
+    if let Some(format) = visualize {
+        cmd_args.push("--visualize".to_string());
+        if format == Visualization::Svg {
+            cmd_args.push("--svg".to_string());
+        }
+    }
+
+    if let Some(size) = size {
+        cmd_args.push("--size".to_string());
+        cmd_args.push(size.to_string());
+    }
+
+    if let Some(runs) = runs {
+        cmd_args.push("--runs".to_string());
+        cmd_args.push(runs.to_string());
+    }
+
     if let Some(submit_part) = submit_part {
         cmd_args.push("--submit".to_string());
         cmd_args.push(submit_part.to_string());
@@ -27,8 +76,13 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
         .args(&cmd_args)
         .stdout(Stdio::inherit())
         .stderr(Stdio::inherit())
-        .spawn()
-        .unwrap();
+        .spawn()?;
+
+    // Failed differential checks, disagreeing variants and missing inputs exit with an error, pass it on.
+    let status = cmd.wait()?;
+    if !status.success() {
+        process::exit(status.code().unwrap_or(1));
+    }
 
-    cmd.wait().unwrap();
+    Ok(())
 }
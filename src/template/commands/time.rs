@@ -1,19 +1,68 @@
 use std::collections::HashSet;
+use std::process;
 
-use crate::template::run_multi::run_multi;
+use crate::template::history::{self, History};
+use crate::template::run_multi::{Profile, run_multi};
 use crate::template::timings::Timings;
-use crate::template::{Day, all_days, readme_benchmarks};
+use crate::template::{ANSI_BOLD, ANSI_RESET, Day, Solution, Year, all_days, readme_benchmarks};
 
-pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
-    let stored_timings = Timings::read_from_file();
+#[allow(clippy::too_many_arguments)]
+pub fn handle(
+    year: Year,
+    day: Option<Day>,
+    run_all: bool,
+    store: bool,
+    jobs: usize,
+    dhat: bool,
+    compare: Option<String>,
+    threshold: f64,
+    solutions: &[Solution],
+) {
+    let stored_timings = Timings::read_from_file(year);
+    let history = History::read_from_file(year);
+
+    // resolve the commit before benching, `--store` modifies the worktree.
+    // NOTE: timings measured with the DHAT allocator are not comparable to regular runs.
+    let commit = (store && !dhat).then(history::worktree_commit);
+
+    if jobs > 1 {
+        eprintln!(
+            "Warning: benching {jobs} days concurrently. Solutions compete for CPU time and caches, timings will be less accurate."
+        );
+    }
+
+    // resolve the baseline before benching, so that a typo does not waste a full run.
+    let baseline = compare.map(|git_ref| {
+        let history = history.as_ref().unwrap_or_else(|e| {
+            eprintln!("Error: could not read benchmark history {e}");
+            process::exit(1);
+        });
+
+        let commit = history::resolve_commit(&git_ref).unwrap_or_else(|e| {
+            eprintln!("Error: {e}");
+            process::exit(1);
+        });
+
+        if history.latest(&commit).is_empty() {
+            eprintln!(
+                "Error: no benchmarks were stored for `{git_ref}` ({commit}). Check it out and run `cargo time --store` first."
+            );
+            process::exit(1);
+        }
+
+        (git_ref, commit)
+    });
 
     let days_to_run = day.map_or_else(
         || {
-            if run_all {
-                all_days().collect()
+            if let (Some((_, commit)), Ok(history)) = (&baseline, &history) {
+                // when comparing, run every day that has a baseline.
+                history.latest(commit).iter().map(|e| e.day).collect()
+            } else if run_all {
+                all_days(year).collect()
             } else {
                 // when the `--all` flag is not set, filter out days that are fully benched.
-                all_days()
+                all_days(year)
                     .filter(|day| !stored_timings.is_day_complete(*day))
                     .collect()
             }
@@ -21,14 +70,35 @@ pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
         |day| HashSet::from([day]),
     );
 
-    let timings = run_multi(&days_to_run, true, true).unwrap();
+    let profile = if dhat {
+        Profile::Dhat
+    } else {
+        Profile::Release
+    };
+
+    let timings = run_multi(year, &days_to_run, profile, true, jobs, solutions, None)
+        .timings
+        .unwrap();
 
     if store {
-        let merged_timings = stored_timings.merge(&timings);
-        merged_timings.store_file().unwrap();
+        let merged_timings = if dhat {
+            // NOTE: only the heap statistics are stored, durations measured with the DHAT allocator are inflated.
+            for timing in &timings.data {
+                if !stored_timings.data.iter().any(|t| t.day == timing.day) {
+                    eprintln!(
+                        "Warning: heap statistics of day {} were not stored, run `cargo time {} --store` first.",
+                        timing.day, timing.day
+                    );
+                }
+            }
+            stored_timings.merge_heap(&timings)
+        } else {
+            stored_timings.merge(&timings)
+        };
+        merged_timings.store_file(year).unwrap();
 
         println!();
-        match readme_benchmarks::update(merged_timings) {
+        match readme_benchmarks::update(year, merged_timings) {
             Ok(()) => {
                 println!("Stored updated benchmarks.");
             }
@@ -36,5 +106,54 @@ pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
                 eprintln!("Failed to store updated benchmarks.");
             }
         }
+
+        if let Some(commit) = commit {
+            store_history(year, history.clone(), commit, &timings);
+        }
+    }
+
+    if let (Some((git_ref, commit)), Ok(history)) = (baseline, &history) {
+        let comparisons = history::compare(&history.latest(&commit), &timings, threshold);
+
+        println!("\n{ANSI_BOLD}Compared to {git_ref}{ANSI_RESET} ({commit})");
+        comparisons.iter().for_each(|c| println!("{c}"));
+
+        let regressions = comparisons.iter().filter(|c| c.is_regression).count();
+
+        if regressions > 0 {
+            eprintln!("\n{regressions} part(s) regressed by more than {threshold}%.");
+            process::exit(1);
+        }
+    }
+}
+
+/// Append timings to the benchmark history, keyed by the checked out commit.
+fn store_history(
+    year: Year,
+    history: Result<History, String>,
+    commit: Result<String, String>,
+    timings: &Timings,
+) {
+    let mut history = match history {
+        Ok(history) => history,
+        Err(e) => {
+            eprintln!(
+                "Warning: benchmarks were not added to the history, could not read it from {e}"
+            );
+            return;
+        }
+    };
+
+    match commit {
+        Ok(commit) => {
+            history.push(&commit, timings);
+
+            if let Err(e) = history.store_file(year) {
+                eprintln!("Failed to store benchmark history: {e}");
+            }
+        }
+        Err(e) => {
+            eprintln!("Warning: benchmarks were not added to the history. {e}");
+        }
     }
 }
@@ -0,0 +1,306 @@
+/// Module that checks the parts of a solution against slow reference implementations on generated inputs.
+use std::{
+    any::Any,
+    cell::Cell,
+    fmt::Display,
+    panic::{self, AssertUnwindSafe},
+    sync::Once,
+};
+
+use crate::template::Generator;
+
+/// A part of a solution or a reference implementation of it.
+pub type Part<T> = fn(&str) -> Option<T>;
+
+/// A (shrunk) input on which a part and its reference implementation disagree.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Counterexample {
+    /// The seed of the generated input that failed first.
+    pub seed: u64,
+    /// The size of the generated input that failed first.
+    pub size: usize,
+    /// The smallest failing input that was found.
+    pub input: String,
+    /// What the reference implementation returned on `input`.
+    pub expected: String,
+    /// What the part returned on `input`.
+    pub actual: String,
+}
+
+impl Display for Counterexample {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "Generated input (size {}, seed {}) shrunk to:",
+            self.size, self.seed
+        )?;
+        writeln!(f, "{}", self.input.trim_end())?;
+        writeln!(f, "Expected (reference): {}", self.expected)?;
+        write!(f, "Actual: {}", self.actual)
+    }
+}
+
+/// What a part returned on an input, or the message it panicked with.
+#[derive(Clone, Debug, PartialEq)]
+enum Outcome<T> {
+    Answer(Option<T>),
+    Panic(String),
+}
+
+impl<T: Display> Display for Outcome<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Outcome::Answer(Some(answer)) => write!(f, "{answer}"),
+            Outcome::Answer(None) => f.write_str("no answer"),
+            Outcome::Panic(message) => write!(f, "panic \"{message}\""),
+        }
+    }
+}
+
+/// How a part and its reference disagree, shrinking keeps the kind of a failure.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Failure {
+    /// Both returned, but with different answers.
+    Mismatch,
+    /// Only the part panicked.
+    PartPanicked,
+    /// Only the reference panicked.
+    ReferencePanicked,
+}
+
+/// Run a part and its reference on an input and classify how they disagree, if they do.
+/// Inputs on which both panic are treated as invalid, e.g. when shrinking breaks the input format.
+fn failure<T: PartialEq>(
+    func: Part<T>,
+    reference: Part<T>,
+    input: &str,
+) -> (Option<Failure>, Outcome<T>, Outcome<T>) {
+    let actual = outcome(func, input);
+    let expected = outcome(reference, input);
+
+    let failure = match (&actual, &expected) {
+        (Outcome::Answer(a), Outcome::Answer(b)) => (a != b).then_some(Failure::Mismatch),
+        (Outcome::Panic(_), Outcome::Answer(_)) => Some(Failure::PartPanicked),
+        (Outcome::Answer(_), Outcome::Panic(_)) => Some(Failure::ReferencePanicked),
+        (Outcome::Panic(_), Outcome::Panic(_)) => None,
+    };
+
+    (failure, actual, expected)
+}
+
+thread_local! {
+    static IS_QUIET: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Run a part, catching panics without printing them.
+fn outcome<T>(func: Part<T>, input: &str) -> Outcome<T> {
+    static HOOK: Once = Once::new();
+
+    // NOTE: the panic hook is global, tests of several days run in parallel threads of one process.
+    // The hook is only installed once and silenced per thread.
+    HOOK.call_once(|| {
+        let hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if !IS_QUIET.with(Cell::get) {
+                hook(info);
+            }
+        }));
+    });
+
+    IS_QUIET.with(|x| x.set(true));
+    let result = panic::catch_unwind(AssertUnwindSafe(|| func(input)));
+    IS_QUIET.with(|x| x.set(false));
+
+    match result {
+        Ok(answer) => Outcome::Answer(answer),
+        Err(payload) => Outcome::Panic(panic_message(payload.as_ref())),
+    }
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        (*message).to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "unknown panic".to_string()
+    }
+}
+
+/// Compare a part with its reference implementation on `runs` generated inputs.
+/// The sizes of the inputs cycle from 1 to `max_size`, the seeds count up from 1.
+///
+/// The first failing input is shrunk, first by generating smaller inputs with the same seed, then
+/// by removing records, see [`shrink`]. It is returned as a counterexample.
+pub fn find_counterexample<T: PartialEq + Display>(
+    func: Part<T>,
+    reference: Part<T>,
+    generator: Generator,
+    runs: u64,
+    max_size: usize,
+) -> Option<Counterexample> {
+    let max_size = max_size.max(1);
+
+    (1..=runs).find_map(|seed| {
+        let size = usize::try_from(seed - 1).map_or(1, |i| i % max_size + 1);
+        let input = generator(size, seed);
+        let (kind, _, _) = failure(func, reference, &input);
+
+        kind.map(|kind| {
+            let fails = |candidate: &str| failure(func, reference, candidate).0 == Some(kind);
+
+            // NOTE: smaller generated inputs are always well-formed, removing records might not be.
+            let input = (1..size)
+                .map(|size| generator(size, seed))
+                .find(|candidate| fails(candidate))
+                .unwrap_or(input);
+            let input = shrink(input, fails);
+            let (_, actual, expected) = failure(func, reference, &input);
+
+            Counterexample {
+                seed,
+                size,
+                input,
+                expected: expected.to_string(),
+                actual: actual.to_string(),
+            }
+        })
+    })
+}
+
+/// Panics with the counterexample if a part and its reference implementation disagree, see [`find_counterexample`].
+pub fn assert_agrees<T: PartialEq + Display>(
+    func: Part<T>,
+    reference: Part<T>,
+    generator: Generator,
+    runs: u64,
+    max_size: usize,
+) {
+    if let Some(counterexample) = find_counterexample(func, reference, generator, runs, max_size) {
+        panic!("part and reference disagree.\n{counterexample}");
+    }
+}
+
+/// Shrink a failing input by removing records for as long as it keeps failing.
+///
+/// Records are the lines of an input, or the comma-separated values of inputs with a single line.
+/// Chunks of records are removed, halving the chunk size until single records can not be removed anymore.
+pub fn shrink(input: String, fails: impl Fn(&str) -> bool) -> String {
+    let (mut records, separator) = split_records(&input);
+    let mut chunk = records.len() / 2;
+
+    while chunk > 0 {
+        let mut start = 0;
+        let mut removed_any = false;
+
+        while start < records.len() {
+            let end = (start + chunk).min(records.len());
+            let candidate: Vec<&str> = records[..start]
+                .iter()
+                .chain(&records[end..])
+                .copied()
+                .collect();
+
+            if !candidate.is_empty() && fails(&join_records(&candidate, separator, &input)) {
+                records = candidate;
+                removed_any = true;
+            } else {
+                start += chunk;
+            }
+        }
+
+        if !removed_any {
+            chunk /= 2;
+        }
+    }
+
+    join_records(&records, separator, &input)
+}
+
+/// Split an input into records, returning them with the separator to join them with.
+fn split_records(input: &str) -> (Vec<&str>, &'static str) {
+    let trimmed = input.trim_end_matches('\n');
+
+    if trimmed.contains('\n') {
+        (trimmed.split('\n').collect(), "\n")
+    } else {
+        (trimmed.split(',').collect(), ",")
+    }
+}
+
+/// Join records, keeping the trailing newline of the original input.
+fn join_records(records: &[&str], separator: &str, original: &str) -> String {
+    let mut joined = records.join(separator);
+    if original.ends_with('\n') {
+        joined.push('\n');
+    }
+    joined
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::*;
+
+    /// Sums a comma-separated list of numbers.
+    fn sum(input: &str) -> Option<u64> {
+        input.trim().split(',').map(|x| x.parse::<u64>().ok()).sum()
+    }
+
+    /// Like [`sum`], but wrong for lists containing 7 after a 3.
+    fn buggy_sum(input: &str) -> Option<u64> {
+        let numbers: Vec<&str> = input.trim().split(',').collect();
+        let seen_three = numbers.iter().position(|x| *x == "3");
+        let seen_seven = numbers.iter().rposition(|x| *x == "7");
+
+        match (seen_three, seen_seven) {
+            (Some(a), Some(b)) if a < b => sum(input).map(|x| x + 1),
+            _ => sum(input),
+        }
+    }
+
+    fn panicking_sum(input: &str) -> Option<u64> {
+        assert!(!input.contains("13"), "unlucky");
+        sum(input)
+    }
+
+    fn generate(size: usize, seed: u64) -> String {
+        let mut rng = crate::template::Rng::new(seed);
+        let numbers: Vec<String> = (0..size).map(|_| rng.range(0..=20).to_string()).collect();
+        format!("{}\n", numbers.join(","))
+    }
+
+    #[test]
+    fn agrees_with_reference() {
+        assert_eq!(find_counterexample(sum, sum, generate, 50, 20), None);
+        assert_agrees(sum, sum, generate, 50, 20);
+    }
+
+    #[test]
+    fn shrinks_counterexamples() {
+        let counterexample = find_counterexample(buggy_sum, sum, generate, 200, 30).unwrap();
+
+        assert_eq!(counterexample.input, "3,7\n");
+        assert_eq!(counterexample.expected, "10");
+        assert_eq!(counterexample.actual, "11");
+        assert!(counterexample.to_string().contains("shrunk to:\n3,7\n"));
+    }
+
+    #[test]
+    fn catches_panics() {
+        let counterexample = find_counterexample(panicking_sum, sum, generate, 200, 30).unwrap();
+
+        assert_eq!(counterexample.input, "13\n");
+        assert_eq!(counterexample.actual, "panic \"unlucky\"");
+    }
+
+    #[test]
+    fn shrinks_lines() {
+        let input = "a\nb\nbad\nc\nd\n".to_string();
+        assert_eq!(shrink(input, |x| x.contains("bad")), "bad\n");
+
+        let input = "1,2,3,4".to_string();
+        assert_eq!(shrink(input, |x| x.contains('2') && x.contains('4')), "2,4");
+    }
+}
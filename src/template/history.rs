@@ -0,0 +1,453 @@
+/// Module that keeps an append-only history of benchmarks, so that timings of different commits can be compared.
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    fs,
+    io::{Error, ErrorKind},
+    process::Command,
+    str::FromStr,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use tinyjson::JsonValue;
+
+use crate::template::timings::Timings;
+use crate::template::{Day, Year};
+
+/// The benchmark of a single day, recorded at a commit.
+#[derive(Clone, Debug, PartialEq)]
+pub struct HistoryEntry {
+    /// Hash of the commit that was checked out when the benchmark ran.
+    pub commit: String,
+    /// Unix timestamp of the benchmark, in seconds.
+    pub recorded_at: f64,
+    pub day: Day,
+    /// Mean duration of part 1, in nanoseconds.
+    pub part_1_nanos: Option<f64>,
+    /// Mean duration of part 2, in nanoseconds.
+    pub part_2_nanos: Option<f64>,
+}
+
+/// All benchmarks of a year. Entries are only ever appended.
+/// Can be serialized from / to JSON.
+#[derive(Clone, Debug, Default)]
+pub struct History {
+    pub data: Vec<HistoryEntry>,
+}
+
+/// The change in duration of a part, compared to a baseline.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PartComparison {
+    pub day: Day,
+    pub part: u8,
+    /// Duration of the baseline, in nanoseconds.
+    pub baseline_nanos: f64,
+    /// Duration of the current run, in nanoseconds.
+    pub current_nanos: f64,
+    /// Whether the part got slower by more than the threshold.
+    pub is_regression: bool,
+}
+
+impl PartComparison {
+    /// Relative change in duration, in percent. Positive values are slowdowns.
+    pub fn change_percent(&self) -> f64 {
+        (self.current_nanos / self.baseline_nanos - 1.0) * 100.0
+    }
+}
+
+impl Display for PartComparison {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let nanos = |x: f64| {
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            Duration::from_nanos(x.round() as u64)
+        };
+
+        let change = self.change_percent();
+        let verdict = if self.is_regression {
+            "regression"
+        } else if change > 0.0 {
+            "slower"
+        } else {
+            "faster"
+        };
+
+        write!(
+            f,
+            "Day {} part {}: {:.1?} → {:.1?} ({change:+.1}%, {verdict})",
+            self.day,
+            self.part,
+            nanos(self.baseline_nanos),
+            nanos(self.current_nanos),
+        )
+    }
+}
+
+impl History {
+    /// Dehydrate the benchmark history of a year to a JSON file.
+    pub fn store_file(&self, year: Year) -> Result<(), Error> {
+        fs::create_dir_all(format!("./data/{year}"))?;
+        let json = JsonValue::from(self.clone());
+        let mut file = fs::File::create(get_path(year))?;
+        json.format_to(&mut file)
+    }
+
+    /// Rehydrate the benchmark history of a year from a JSON file. If not present, returns an empty history.
+    /// Fails if the file can not be read or parsed, so that a corrupt history is never overwritten.
+    pub fn read_from_file(year: Year) -> Result<Self, String> {
+        let path = get_path(year);
+
+        match fs::read_to_string(&path) {
+            Ok(contents) => History::try_from(contents).map_err(|e| format!("\"{path}\": {e}")),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(History::default()),
+            Err(e) => Err(format!("\"{path}\": {e}")),
+        }
+    }
+
+    /// Append the timings of a benchmark run at a commit.
+    pub fn push(&mut self, commit: &str, timings: &Timings) {
+        let recorded_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|x| x.as_secs_f64())
+            .unwrap_or_default();
+
+        for timing in &timings.data {
+            self.data.push(HistoryEntry {
+                commit: commit.into(),
+                recorded_at,
+                day: timing.day,
+                part_1_nanos: timing.part_1_stats.as_ref().map(|s| s.mean),
+                part_2_nanos: timing.part_2_stats.as_ref().map(|s| s.mean),
+            });
+        }
+    }
+
+    /// The most recent benchmark of every day that was recorded at a commit, sorted by day.
+    pub fn latest(&self, commit: &str) -> Vec<&HistoryEntry> {
+        let mut latest: HashMap<Day, &HistoryEntry> = HashMap::new();
+
+        for entry in self.data.iter().filter(|e| e.commit == commit) {
+            let is_newer = latest
+                .get(&entry.day)
+                .is_none_or(|x| x.recorded_at <= entry.recorded_at);
+
+            if is_newer {
+                latest.insert(entry.day, entry);
+            }
+        }
+
+        let mut entries: Vec<_> = latest.into_values().collect();
+        entries.sort_unstable_by_key(|e| e.day);
+        entries
+    }
+}
+
+/// Compare timings against a baseline. Parts that are missing on either side are skipped.
+/// A part regresses if its duration grew by more than `threshold` percent.
+pub fn compare(
+    baseline: &[&HistoryEntry],
+    timings: &Timings,
+    threshold: f64,
+) -> Vec<PartComparison> {
+    let mut comparisons = vec![];
+
+    for timing in &timings.data {
+        let Some(entry) = baseline.iter().find(|e| e.day == timing.day) else {
+            continue;
+        };
+
+        let parts = [
+            (1, entry.part_1_nanos, timing.part_1_stats.as_ref()),
+            (2, entry.part_2_nanos, timing.part_2_stats.as_ref()),
+        ];
+
+        for (part, baseline_nanos, current) in parts {
+            let (Some(baseline_nanos), Some(current)) = (baseline_nanos, current) else {
+                continue;
+            };
+
+            let mut comparison = PartComparison {
+                day: timing.day,
+                part,
+                baseline_nanos,
+                current_nanos: current.mean,
+                is_regression: false,
+            };
+            comparison.is_regression = comparison.change_percent() > threshold;
+            comparisons.push(comparison);
+        }
+    }
+
+    comparisons
+}
+
+/// Resolve a git ref (e.g. `HEAD`, `main` or a hash) to a commit hash.
+pub fn resolve_commit(git_ref: &str) -> Result<String, String> {
+    let output = Command::new("git")
+        .args(["rev-parse", "--verify", "--quiet"])
+        .arg(format!("{git_ref}^{{commit}}"))
+        .output()
+        .map_err(|e| format!("Could not run git: {e}."))?;
+
+    if !output.status.success() {
+        return Err(format!("`{git_ref}` is not a commit."));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Resolve the commit that benchmarks of the worktree are recorded under.
+/// Fails if the worktree has uncommitted changes, since the timings would not belong to `HEAD`.
+/// The benchmarks and readme that `cargo time --store` writes, as well as inputs, are not considered changes.
+pub fn worktree_commit() -> Result<String, String> {
+    let output = Command::new("git")
+        .args([
+            "status",
+            "--porcelain",
+            "--",
+            ".",
+            ":(exclude)data",
+            ":(exclude)README.md",
+        ])
+        .output()
+        .map_err(|e| format!("Could not run git: {e}."))?;
+
+    if !output.status.success() {
+        return Err("Could not read the status of the worktree.".into());
+    }
+
+    if !output.stdout.is_empty() {
+        return Err("The worktree has uncommitted changes, commit them first.".into());
+    }
+
+    resolve_commit("HEAD")
+}
+
+fn get_path(year: Year) -> String {
+    format!("./data/{year}/history.json")
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<History> for JsonValue {
+    fn from(value: History) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for History {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json_data = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("data")
+            .ok_or("expected JSON document to have key `data`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.data` to be an array.")?;
+
+        Ok(History {
+            data: json_data
+                .iter()
+                .map(HistoryEntry::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&HistoryEntry> for JsonValue {
+    fn from(value: &HistoryEntry) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("commit".into(), JsonValue::String(value.commit.clone()));
+        map.insert("recorded_at".into(), JsonValue::Number(value.recorded_at));
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert(
+            "part_1_nanos".into(),
+            value
+                .part_1_nanos
+                .map_or(JsonValue::Null, JsonValue::Number),
+        );
+        map.insert(
+            "part_2_nanos".into(),
+            value
+                .part_2_nanos
+                .map_or(JsonValue::Null, JsonValue::Number),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for HistoryEntry {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected history entry to be a JSON object.")?;
+
+        let commit = json
+            .get("commit")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected history_entry.commit to be a string.")?;
+
+        let recorded_at = json
+            .get("recorded_at")
+            .and_then(|v| v.get::<f64>().copied())
+            .ok_or("Expected history_entry.recorded_at to be a number.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected history_entry.day to be a Day struct.")?;
+
+        let part_1_nanos = json
+            .get("part_1_nanos")
+            .map(|v| if v.is_null() { None } else { v.get::<f64>() })
+            .ok_or("Expected history_entry.part_1_nanos to be null or a number.")?;
+
+        let part_2_nanos = json
+            .get("part_2_nanos")
+            .map(|v| if v.is_null() { None } else { v.get::<f64>() })
+            .ok_or("Expected history_entry.part_2_nanos to be null or a number.")?;
+
+        Ok(HistoryEntry {
+            commit: commit.clone(),
+            recorded_at,
+            day,
+            part_1_nanos: part_1_nanos.copied(),
+            part_2_nanos: part_2_nanos.copied(),
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use crate::{
+        day,
+        template::timings::{Statistics, Timing, Timings},
+    };
+
+    use super::{History, HistoryEntry, compare};
+
+    fn get_mock_timing(day: u8, part_1: f64, part_2: Option<f64>) -> Timing {
+        let stats = |mean: f64| Statistics {
+            mean,
+            min: mean,
+            median: mean,
+            p95: mean,
+            max: mean,
+            std_dev: 0.0,
+        };
+
+        Timing {
+            day: crate::template::Day::new(day).unwrap(),
+            part_1: Some(format!("{part_1}ns")),
+            part_2: part_2.map(|x| format!("{x}ns")),
+            part_1_stats: Some(stats(part_1)),
+            part_2_stats: part_2.map(stats),
+            part_1_heap: None,
+            part_2_heap: None,
+            total_nanos: part_1 + part_2.unwrap_or_default(),
+        }
+    }
+
+    fn get_mock_entry(commit: &str, recorded_at: f64, day: u8, part_1: f64) -> HistoryEntry {
+        HistoryEntry {
+            commit: commit.into(),
+            recorded_at,
+            day: crate::template::Day::new(day).unwrap(),
+            part_1_nanos: Some(part_1),
+            part_2_nanos: None,
+        }
+    }
+
+    #[test]
+    fn appends_timings() {
+        let mut history = History::default();
+        let timings = Timings {
+            data: vec![get_mock_timing(1, 100.0, Some(200.0))],
+        };
+
+        history.push("abc", &timings);
+        history.push("abc", &timings);
+
+        assert_eq!(history.data.len(), 2);
+        assert_eq!(history.data[0].part_1_nanos, Some(100.0));
+        assert_eq!(history.data[0].part_2_nanos, Some(200.0));
+    }
+
+    #[test]
+    fn finds_latest_entries_of_commit() {
+        let history = History {
+            data: vec![
+                get_mock_entry("abc", 1.0, 2, 100.0),
+                get_mock_entry("abc", 1.0, 1, 100.0),
+                get_mock_entry("def", 2.0, 1, 300.0),
+                get_mock_entry("abc", 3.0, 1, 200.0),
+            ],
+        };
+
+        let latest = history.latest("abc");
+        assert_eq!(latest.len(), 2);
+        assert_eq!(latest[0].day, day!(1));
+        assert_eq!(latest[0].part_1_nanos, Some(200.0));
+        assert_eq!(latest[1].day, day!(2));
+        assert!(history.latest("123").is_empty());
+    }
+
+    #[test]
+    fn detects_regressions() {
+        let baseline = [
+            &get_mock_entry("abc", 1.0, 1, 100.0),
+            &get_mock_entry("abc", 1.0, 2, 100.0),
+        ];
+        let timings = Timings {
+            data: vec![
+                get_mock_timing(1, 105.0, Some(1.0)),
+                get_mock_timing(2, 120.0, None),
+                get_mock_timing(3, 500.0, None),
+            ],
+        };
+
+        let comparisons = compare(&baseline, &timings, 10.0);
+        assert_eq!(comparisons.len(), 2);
+        assert_eq!(comparisons[0].day, day!(1));
+        assert!(!comparisons[0].is_regression);
+        assert_eq!(comparisons[1].day, day!(2));
+        assert!(comparisons[1].is_regression);
+        assert!((comparisons[1].change_percent() - 20.0).abs() < 1e-9);
+        assert_eq!(
+            comparisons[1].to_string(),
+            "Day 02 part 1: 100.0ns → 120.0ns (+20.0%, regression)"
+        );
+    }
+
+    #[test]
+    fn roundtrips_json() {
+        let history = History {
+            data: vec![
+                get_mock_entry("abc", 1.5, 1, 100.0),
+                get_mock_entry("def", 2.5, 2, 200.0),
+            ],
+        };
+
+        let json = tinyjson::JsonValue::from(history.clone());
+        let reparsed = History::try_from(json.stringify().unwrap()).unwrap();
+        assert_eq!(reparsed.data, history.data);
+    }
+}
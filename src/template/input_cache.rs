@@ -0,0 +1,337 @@
+/// Module that keeps track of downloaded inputs, so that they are not fetched again and damaged inputs can be detected.
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    fs,
+    io::{Error, ErrorKind},
+    str::FromStr,
+    time::{SystemTime, UNIX_EPOCH},
+};
+use tinyjson::JsonValue;
+
+use crate::template::{Day, Year};
+
+/// Metadata of a downloaded input.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CachedInput {
+    pub day: Day,
+    pub year: Year,
+    /// FNV-1a hash of the input, as hex string.
+    pub hash: String,
+    /// Length of the input in bytes.
+    pub size: usize,
+    /// Unix timestamp of the download, in seconds.
+    pub fetched_at: f64,
+}
+
+/// Metadata of all downloaded inputs.
+/// Can be serialized from / to JSON.
+#[derive(Clone, Debug, Default)]
+pub struct InputCache {
+    pub data: Vec<CachedInput>,
+}
+
+/// A problem with an input file, detected before running a solution against it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum InputProblem {
+    /// The input is empty or only contains whitespace.
+    Empty,
+    /// The input is shorter than the downloaded input.
+    Truncated { expected: usize, actual: usize },
+    /// The input has the same length or is longer than the downloaded input, but differs from it.
+    Modified,
+    /// There is no metadata for the input, and it does not end with a newline like downloaded inputs do.
+    MissingNewline,
+}
+
+impl InputProblem {
+    /// Whether the problem prevents running a solution.
+    pub fn is_fatal(&self) -> bool {
+        *self == InputProblem::Empty
+    }
+}
+
+impl Display for InputProblem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InputProblem::Empty => write!(f, "is empty"),
+            InputProblem::Truncated { expected, actual } => write!(
+                f,
+                "looks truncated, it has {actual} bytes but {expected} bytes were downloaded"
+            ),
+            InputProblem::Modified => write!(f, "differs from the downloaded input"),
+            InputProblem::MissingNewline => {
+                write!(f, "does not end with a newline, it might be truncated")
+            }
+        }
+    }
+}
+
+impl InputCache {
+    /// Dehydrate the input metadata of a year to a JSON file.
+    pub fn store_file(&self, year: Year) -> Result<(), Error> {
+        fs::create_dir_all(format!("./data/{year}"))?;
+        let json = JsonValue::from(self.clone());
+        let mut file = fs::File::create(get_path(year))?;
+        json.format_to(&mut file)
+    }
+
+    /// Rehydrate the input metadata of a year from a JSON file. If not present, returns an empty cache.
+    /// Fails if the file can not be read or parsed, so that the metadata of other days is never overwritten.
+    pub fn read_from_file(year: Year) -> Result<Self, String> {
+        let path = get_path(year);
+
+        match fs::read_to_string(&path) {
+            Ok(contents) => InputCache::try_from(contents).map_err(|e| format!("\"{path}\": {e}")),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(InputCache::default()),
+            Err(e) => Err(format!("\"{path}\": {e}")),
+        }
+    }
+
+    pub fn get(&self, day: Day) -> Option<&CachedInput> {
+        self.data.iter().find(|x| x.day == day)
+    }
+
+    /// Record an input that was downloaded just now, replacing earlier metadata for the day.
+    pub fn insert(&mut self, day: Day, year: Year, input: &str) {
+        let fetched_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|x| x.as_secs_f64())
+            .unwrap_or_default();
+
+        self.data.retain(|x| x.day != day);
+        self.data.push(CachedInput {
+            day,
+            year,
+            hash: hash(input),
+            size: input.len(),
+            fetched_at,
+        });
+        self.data.sort_unstable_by_key(|x| x.day);
+    }
+
+    /// Whether an input is the unchanged download of a day and year.
+    pub fn is_valid(&self, day: Day, year: Year, input: &str) -> bool {
+        self.get(day)
+            .is_some_and(|x| x.year == year && x.hash == hash(input) && !input.trim().is_empty())
+    }
+
+    /// Check an input for problems, comparing it to the downloaded input if metadata is present.
+    pub fn check(&self, day: Day, input: &str) -> Result<(), InputProblem> {
+        if input.trim().is_empty() {
+            return Err(InputProblem::Empty);
+        }
+
+        match self.get(day) {
+            Some(cached) if cached.hash != hash(input) => {
+                if input.len() < cached.size {
+                    Err(InputProblem::Truncated {
+                        expected: cached.size,
+                        actual: input.len(),
+                    })
+                } else {
+                    Err(InputProblem::Modified)
+                }
+            }
+            Some(_) => Ok(()),
+            None if !input.ends_with('\n') => Err(InputProblem::MissingNewline),
+            None => Ok(()),
+        }
+    }
+}
+
+fn get_path(year: Year) -> String {
+    format!("./data/{year}/inputs.json")
+}
+
+/// 64-bit FNV-1a hash of a string, as hex string.
+fn hash(input: &str) -> String {
+    let hash = input.bytes().fold(0xcbf2_9ce4_8422_2325_u64, |acc, b| {
+        (acc ^ u64::from(b)).wrapping_mul(0x0000_0100_0000_01b3)
+    });
+    format!("{hash:016x}")
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<InputCache> for JsonValue {
+    fn from(value: InputCache) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for InputCache {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json_data = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("data")
+            .ok_or("expected JSON document to have key `data`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.data` to be an array.")?;
+
+        Ok(InputCache {
+            data: json_data
+                .iter()
+                .map(CachedInput::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&CachedInput> for JsonValue {
+    fn from(value: &CachedInput) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert(
+            "year".into(),
+            JsonValue::Number(f64::from(value.year.into_inner())),
+        );
+        map.insert("hash".into(), JsonValue::String(value.hash.clone()));
+        #[allow(clippy::cast_precision_loss)]
+        map.insert("size".into(), JsonValue::Number(value.size as f64));
+        map.insert("fetched_at".into(), JsonValue::Number(value.fetched_at));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for CachedInput {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected cached input to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected cached_input.day to be a Day struct.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let year = json
+            .get("year")
+            .and_then(|v| v.get::<f64>())
+            .and_then(|x| Year::new(*x as u16))
+            .ok_or("Expected cached_input.year to be a year.")?;
+
+        let hash = json
+            .get("hash")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected cached_input.hash to be a string.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let size = json
+            .get("size")
+            .and_then(|v| v.get::<f64>())
+            .map(|x| *x as usize)
+            .ok_or("Expected cached_input.size to be a number.")?;
+
+        let fetched_at = json
+            .get("fetched_at")
+            .and_then(|v| v.get::<f64>().copied())
+            .ok_or("Expected cached_input.fetched_at to be a number.")?;
+
+        Ok(CachedInput {
+            day,
+            year,
+            hash: hash.clone(),
+            size,
+            fetched_at,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use crate::{day, year};
+
+    use super::{InputCache, InputProblem, hash};
+
+    fn get_mock_cache() -> InputCache {
+        let mut cache = InputCache::default();
+        cache.insert(day!(1), year!(2025), "L68\nL30\n");
+        cache.insert(day!(2), year!(2024), "11-22\n");
+        cache
+    }
+
+    #[test]
+    fn hashes_inputs() {
+        assert_eq!(hash(""), "cbf29ce484222325");
+        assert_eq!(hash("a"), "af63dc4c8601ec8c");
+        assert_ne!(hash("L68\nL30\n"), hash("L68\nL30"));
+    }
+
+    #[test]
+    fn validates_cached_inputs() {
+        let cache = get_mock_cache();
+
+        assert!(cache.is_valid(day!(1), year!(2025), "L68\nL30\n"));
+        assert!(!cache.is_valid(day!(1), year!(2025), "L68\n"));
+        assert!(!cache.is_valid(day!(1), year!(2024), "L68\nL30\n"));
+        assert!(!cache.is_valid(day!(2), year!(2025), "11-22\n"));
+        assert!(!cache.is_valid(day!(3), year!(2025), "1\n"));
+    }
+
+    #[test]
+    fn replaces_cached_inputs() {
+        let mut cache = get_mock_cache();
+        cache.insert(day!(1), year!(2025), "R1\n");
+
+        assert_eq!(cache.data.len(), 2);
+        assert!(cache.is_valid(day!(1), year!(2025), "R1\n"));
+        assert_eq!(cache.get(day!(1)).unwrap().size, 3);
+    }
+
+    #[test]
+    fn detects_input_problems() {
+        let cache = get_mock_cache();
+
+        assert_eq!(cache.check(day!(1), "L68\nL30\n"), Ok(()));
+        assert_eq!(cache.check(day!(1), " \n"), Err(InputProblem::Empty));
+        assert_eq!(
+            cache.check(day!(1), "L68\nL3"),
+            Err(InputProblem::Truncated {
+                expected: 8,
+                actual: 6
+            })
+        );
+        assert_eq!(
+            cache.check(day!(1), "L68\nL31\n"),
+            Err(InputProblem::Modified)
+        );
+        assert_eq!(cache.check(day!(3), "1\n2\n"), Ok(()));
+        assert_eq!(
+            cache.check(day!(3), "1\n2"),
+            Err(InputProblem::MissingNewline)
+        );
+        assert!(InputProblem::Empty.is_fatal());
+        assert!(!InputProblem::Modified.is_fatal());
+    }
+
+    #[test]
+    fn roundtrips_json() {
+        let cache = get_mock_cache();
+        let json = tinyjson::JsonValue::from(cache.clone());
+        let reparsed = InputCache::try_from(json.stringify().unwrap()).unwrap();
+        assert_eq!(reparsed.data, cache.data);
+    }
+}
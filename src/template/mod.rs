@@ -1,68 +1,303 @@
 use std::{env, fs};
 
-pub mod aoc_cli;
+#[cfg(feature = "client")]
+pub mod aoc_client;
 pub mod commands;
+pub mod differential;
 pub mod runner;
 
 pub use day::*;
+pub use puzzle::*;
+pub use rng::Rng;
+pub use year::*;
 
+mod answers;
 mod day;
+mod history;
+mod input_cache;
+mod puzzle;
 mod readme_benchmarks;
+mod rng;
 mod run_multi;
+mod scaling;
+mod submissions;
 mod timings;
+mod year;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
 
-/// Helper function that reads a text file to a string.
+/// A solution that is linked into the main binary, so that it can be run without spawning `cargo`.
+/// The list of linked solutions is generated by `build.rs` from the binaries in `src/bin/<year>`.
+#[derive(Clone, Copy)]
+pub struct Solution {
+    pub puzzle: Puzzle,
+    /// Runs all parts of the solution against an input, benching them if the flag is set.
+    pub run: fn(&str, bool) -> Vec<runner::PartResult>,
+    /// Generates inputs for the solution, if it registered a [`Generator`].
+    pub generator: Option<Generator>,
+}
+
+impl Solution {
+    /// Panics unless the [`Generator`] of the solution, if any, is deterministic and creates inputs
+    /// that every part solves. Solutions that register a generator check this in their tests, see `solution!`.
+    pub fn assert_generator(&self) {
+        let Some(generate) = self.generator else {
+            return;
+        };
+
+        for size in [1, 20] {
+            let input = generate(size, 7);
+            assert_eq!(
+                input,
+                generate(size, 7),
+                "{}: generated different inputs for size {size} and the same seed.",
+                self.puzzle
+            );
+
+            for result in (self.run)(&input, false) {
+                assert!(
+                    result.answer.is_some(),
+                    "{}: part {} has no answer for a generated input of size {size}:\n{input}",
+                    self.puzzle,
+                    result.part
+                );
+            }
+        }
+
+        assert_ne!(
+            generate(20, 7),
+            generate(20, 8),
+            "{}: generated the same input for different seeds.",
+            self.puzzle
+        );
+    }
+}
+
+/// Generates an input of a day with about `size` records (e.g. lines), see `solution!`.
+/// The same size and seed always generate the same input, see [`Rng`].
+pub type Generator = fn(size: usize, seed: u64) -> String;
+
+/// The formats that a [`Visualizer`] can render, selected with `cargo solve <day> --visualize [--svg]`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Visualization {
+    /// Plain text, e.g. ASCII art of a grid.
+    Text,
+    /// A standalone SVG document.
+    Svg,
+}
+
+/// Renders how a solution solves an input, see `solution!`.
+/// Returns an error message for invalid inputs or formats that it can not render.
+pub type Visualizer = fn(input: &str, format: Visualization) -> Result<String, String>;
+
+/// Helper function that reads a text file of a puzzle to a string.
 #[must_use]
-pub fn read_file(folder: &str, day: Day) -> String {
+pub fn read_file(folder: &str, puzzle: Puzzle) -> String {
     let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
+    let filepath = cwd.join(puzzle.data_path(folder, "txt"));
     let f = fs::read_to_string(filepath);
     f.expect("could not open input file")
 }
 
-/// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
+/// Helper function that reads the input of a puzzle and checks it for problems, see [`input_cache::InputProblem`].
+/// Returns an error message if the input can not be used, non-fatal problems are reported to stderr.
+pub fn try_read_input(puzzle: Puzzle) -> Result<String, String> {
+    let path = puzzle.data_path("inputs", "txt");
+    let input = fs::read_to_string(&path)
+        .map_err(|e| format!("Could not open input file \"{path}\": {e}."))?;
+    let download = format!(
+        "cargo download {} --year {}",
+        puzzle.day.into_inner(),
+        puzzle.year
+    );
+
+    let cache = match input_cache::InputCache::read_from_file(puzzle.year) {
+        Ok(cache) => cache,
+        Err(e) => {
+            eprintln!(
+                "Warning: input file \"{path}\" was not checked, could not read input metadata from {e}"
+            );
+            return Ok(input);
+        }
+    };
+
+    match cache.check(puzzle.day, &input) {
+        Ok(()) => Ok(input),
+        Err(problem) if problem.is_fatal() => Err(format!(
+            "Input file \"{path}\" {problem}. Run `{download}` or paste your puzzle input into it."
+        )),
+        Err(problem) => {
+            eprintln!("Warning: input file \"{path}\" {problem}. Run `{download}` to restore it.");
+            Ok(input)
+        }
+    }
+}
+
+/// Helper function that reads the input of a puzzle, exiting if it can not be used.
 #[must_use]
-pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
+pub fn read_input(puzzle: Puzzle) -> String {
+    try_read_input(puzzle).unwrap_or_else(|e| {
+        eprintln!("{e}");
+        std::process::exit(1);
+    })
+}
+
+/// Helper function that reads a text file of a puzzle to string, appending a part suffix. E.g. like `01-2.txt`.
+#[must_use]
+pub fn read_file_part(folder: &str, puzzle: Puzzle, part: u8) -> String {
     let cwd = env::current_dir().unwrap();
     let filepath = cwd
         .join("data")
+        .join(puzzle.year.to_string())
         .join(folder)
-        .join(format!("{day}-{part}.txt"));
+        .join(format!("{}-{part}.txt", puzzle.day));
     let f = fs::read_to_string(filepath);
     f.expect("could not open input file")
 }
 
-/// Creates the constant `DAY` and sets up the input and runner for each part.
+/// Creates the constants `YEAR`, `DAY` and `PUZZLE` and sets up the input and runner for each part.
+///
+/// The optional, third parameter (1 or 2) allows you to only run a single part of the solution.
+///
+/// Alternative implementations of a part can be registered as variants instead, which are benched
+/// side by side with `cargo solve <day> --variants`. Slow but obviously correct reference implementations
+/// of a part can be registered as references, which `cargo solve <day> --differential` compares the part
+/// with on generated inputs. A [`Generator`] for inputs of any size is registered as well, which
+/// `cargo generate <day>` prints and `cargo solve <day> --scaling` uses to estimate the complexity of each part.
+/// Finally, a [`Visualizer`] of the input can be registered, which `cargo solve <day> --visualize` prints:
 ///
-/// The optional, second parameter (1 or 2) allows you to only run a single part of the solution.
+/// ```ignore
+/// advent_of_code::solution!(2025, 1, variants: {
+///     part_one: [part_one_fold],
+/// }, references: {
+///     part_two: part_two_brute_force,
+/// }, generator: generate, visualizer: visualize);
+/// ```
 #[macro_export]
 macro_rules! solution {
-    ($day:expr) => {
-        $crate::solution!(@impl $day, [part_one, 1] [part_two, 2]);
+    ($year:expr, $day:expr) => {
+        $crate::solution!(@impl $year, $day, {}, {}, [], [], [part_one, 1] [part_two, 2]);
     };
-    ($day:expr, 1) => {
-        $crate::solution!(@impl $day, [part_one, 1]);
+    ($year:expr, $day:expr, 1) => {
+        $crate::solution!(@impl $year, $day, {}, {}, [], [], [part_one, 1]);
     };
-    ($day:expr, 2) => {
-        $crate::solution!(@impl $day, [part_two, 2]);
+    ($year:expr, $day:expr, 2) => {
+        $crate::solution!(@impl $year, $day, {}, {}, [], [], [part_two, 2]);
     };
+    (
+        $year:expr, $day:expr
+        $(, variants: { $( $base:ident: [$( $variant:ident ),* $(,)?] ),* $(,)? } )?
+        $(, references: { $( $checked:ident: $reference:ident ),* $(,)? } )?
+        $(, generator: $generator:path )?
+        $(, visualizer: $visualizer:path )?
+        $(,)?
+    ) => {
+        $crate::solution!(
+            @impl $year, $day,
+            { $( $( $base: [$( $variant ),*] )* )? },
+            { $( $( $checked: $reference )* )? },
+            [$( $generator )?],
+            [$( $visualizer )?],
+            [part_one, 1] [part_two, 2]
+        );
+    };
+
+    (@option) => { None };
+    (@option $function:path) => { Some($function) };
+
+    (
+        @impl $year:expr, $day:expr,
+        { $( $base:ident: [$( $variant:ident ),*] )* },
+        { $( $checked:ident: $reference:ident )* },
+        [$( $generator:path )?],
+        [$( $visualizer:path )?],
+        $( [$func:expr, $part:expr] )*
+    ) => {
+        /// The year of the current puzzle.
+        pub const YEAR: $crate::template::Year = $crate::year!($year);
 
-    (@impl $day:expr, $( [$func:expr, $part:expr] )*) => {
         /// The current day.
-        const DAY: $crate::template::Day = $crate::day!($day);
+        pub const DAY: $crate::template::Day = $crate::day!($day);
+
+        /// The current puzzle.
+        pub const PUZZLE: $crate::template::Puzzle = $crate::template::Puzzle::new(YEAR, DAY);
+
+        const _: () = assert!(YEAR.has_day(DAY), "invalid day number, the year has fewer puzzles");
+
+        /// Generates inputs of any size for this day, if registered.
+        pub const GENERATOR: Option<$crate::template::Generator> = $crate::solution!(@option $( $generator )?);
+
+        /// Renders how this day solves an input, if registered.
+        pub const VISUALIZER: Option<$crate::template::Visualizer> = $crate::solution!(@option $( $visualizer )?);
 
         #[cfg(feature = "dhat-heap")]
         #[global_allocator]
         static ALLOC: dhat::Alloc = dhat::Alloc;
 
+        // NOTE: solutions are also compiled as modules of the main binary, where `main` is unused.
+        #[allow(dead_code)]
         fn main() {
             use $crate::template::runner::*;
-            let input = $crate::template::read_file("inputs", DAY);
-            $( run_part($func, &input, DAY, $part); )*
+
+            if std::env::args().any(|x| x == "--scaling") {
+                let inputs = scaling_inputs(PUZZLE, GENERATOR);
+                $( run_scaling($func, $part, &inputs); )*
+                return;
+            }
+
+            if std::env::args().any(|x| x == "--differential") {
+                let agreements: &[bool] = &[$(
+                    check_reference(
+                        (stringify!($checked), $checked as fn(&str) -> Option<_>),
+                        (stringify!($reference), $reference as fn(&str) -> Option<_>),
+                        GENERATOR,
+                    ),
+                )*];
+                finish_references(agreements);
+                return;
+            }
+
+            let input = $crate::template::read_input(PUZZLE);
+
+            if std::env::args().any(|x| x == "--visualize") {
+                visualize(&input, VISUALIZER);
+                return;
+            }
+
+            if std::env::args().any(|x| x == "--variants") {
+                let agreements: &[bool] = &[$(
+                    compare_variants(&input, &[
+                        (stringify!($base), $base as fn(&str) -> Option<_>),
+                        $( (stringify!($variant), $variant as fn(&str) -> Option<_>), )*
+                    ]),
+                )*];
+                finish_variants(agreements);
+                return;
+            }
+
+            $( run_part($func, &input, PUZZLE, $part); )*
         }
+
+        /// Runs the parts of this day in-process, see [`Solution`]($crate::template::Solution).
+        #[allow(dead_code)]
+        pub fn run_parts(input: &str, is_timed: bool) -> Vec<$crate::template::runner::PartResult> {
+            use $crate::template::runner::*;
+            vec![$( measure_part($func, input, DAY, $part, is_timed), )*]
+        }
+
+        $(
+            #[cfg(test)]
+            #[test]
+            fn test_generator() {
+                let solution = $crate::template::Solution {
+                    puzzle: PUZZLE,
+                    run: run_parts,
+                    generator: Some($generator),
+                };
+                solution.assert_generator();
+            }
+        )?
     };
 }
@@ -0,0 +1,43 @@
+use std::fmt::Display;
+
+use crate::template::{Day, Year};
+
+/// Identifies a puzzle by its year and day.
+///
+/// # Display
+/// This value displays as `<year>-<day>`, which is also the name of the binary of its solution.
+///
+/// ```
+/// # use advent_of_code::template::{Day, Puzzle, Year};
+/// let puzzle = Puzzle::new(Year::new(2025).unwrap(), Day::new(8).unwrap());
+/// assert_eq!(puzzle.to_string(), "2025-08")
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Puzzle {
+    pub year: Year,
+    pub day: Day,
+}
+
+impl Puzzle {
+    pub const fn new(year: Year, day: Day) -> Self {
+        Self { year, day }
+    }
+
+    /// Path of the solution of this puzzle.
+    #[must_use]
+    pub fn bin_path(&self) -> String {
+        format!("./src/bin/{}/{}.rs", self.year, self.day)
+    }
+
+    /// Path of a data file of this puzzle, e.g. `data/2025/inputs/01.txt`.
+    #[must_use]
+    pub fn data_path(&self, folder: &str, extension: &str) -> String {
+        format!("data/{}/{folder}/{}.{extension}", self.year, self.day)
+    }
+}
+
+impl Display for Puzzle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}-{}", self.year, self.day)
+    }
+}
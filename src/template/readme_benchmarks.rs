@@ -2,10 +2,10 @@
 /// The approach taken is similar to how `aoc-readme-stars` handles this.
 use std::{fs, io};
 
-use crate::template::Day;
-use crate::template::timings::Timings;
+use crate::template::timings::{HeapStatistics, Timings, format_bytes};
+use crate::template::{Puzzle, Year};
 
-static MARKER: &str = "<!--- benchmarking table --->";
+static MARKER_PREFIX: &str = "<!--- benchmarking table";
 
 #[allow(dead_code)]
 #[derive(Debug)]
@@ -25,13 +25,13 @@ pub struct TablePosition {
     pos_end: usize,
 }
 
-#[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+/// Every year has its own table, enclosed by a pair of markers.
+fn get_marker(year: Year) -> String {
+    format!("{MARKER_PREFIX} {year} --->")
 }
 
-fn locate_table(readme: &str) -> Result<TablePosition, Error> {
-    let matches: Vec<_> = readme.match_indices(MARKER).collect();
+fn locate_table(readme: &str, marker: &str) -> Result<TablePosition, Error> {
+    let matches: Vec<_> = readme.match_indices(marker).collect();
 
     if matches.len() > 2 {
         return Err(Error::Parser(
@@ -52,55 +52,111 @@ fn locate_table(readme: &str) -> Result<TablePosition, Error> {
     Ok(TablePosition { pos_start, pos_end })
 }
 
-fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String {
-    let header = format!("{prefix} Benchmarks");
+fn construct_table(prefix: &str, year: Year, timings: Timings, total_millis: f64) -> String {
+    let header = format!("{prefix} {year} Benchmarks");
+    let marker = get_marker(year);
 
-    let mut lines: Vec<String> = vec![
-        MARKER.into(),
-        header,
-        String::new(),
-        "| Day | Part 1 | Part 2 |".into(),
-        "| :---: | :---: | :---:  |".into(),
-    ];
+    // NOTE: heap statistics are only measured with DHAT, their columns are omitted if there are none.
+    let has_heap = timings
+        .data
+        .iter()
+        .any(|t| t.part_1_heap.is_some() || t.part_2_heap.is_some());
+
+    let mut lines: Vec<String> = vec![marker.clone(), header, String::new()];
+
+    if has_heap {
+        lines.push("| Day | Part 1 | Part 2 | Part 1 (heap) | Part 2 (heap) |".into());
+        lines.push("| :---: | :---: | :---:  | :---: | :---: |".into());
+    } else {
+        lines.push("| Day | Part 1 | Part 2 |".into());
+        lines.push("| :---: | :---: | :---:  |".into());
+    }
 
     for timing in timings.data {
-        let path = get_path_for_bin(timing.day);
-        lines.push(format!(
+        let path = Puzzle::new(year, timing.day).bin_path();
+        let mut line = format!(
             "| [Day {}]({}) | `{}` | `{}` |",
             timing.day.into_inner(),
             path,
             timing.part_1.unwrap_or_else(|| "-".into()),
             timing.part_2.unwrap_or_else(|| "-".into())
-        ));
+        );
+
+        if has_heap {
+            line.push_str(&format!(
+                " {} | {} |",
+                format_heap(timing.part_1_heap.as_ref()),
+                format_heap(timing.part_2_heap.as_ref())
+            ));
+        }
+
+        lines.push(line);
     }
 
     lines.push(String::new());
     lines.push(format!("**Total: {total_millis:.2}ms**"));
-    lines.push(MARKER.into());
+    lines.push(marker);
 
     lines.join("\n")
 }
 
-fn update_content(s: &mut String, timings: Timings, total_millis: f64) -> Result<(), Error> {
-    let positions = locate_table(s)?;
-    let table = construct_table("##", timings, total_millis);
-    s.replace_range(positions.pos_start..positions.pos_end, &table);
+/// Formats the heap usage of a part as a table cell, e.g. `` `232 B` peak · 3 allocs · `276 B` total ``.
+fn format_heap(heap: Option<&HeapStatistics>) -> String {
+    match heap {
+        Some(heap) => format!(
+            "`{}` peak · {} allocs · `{}` total",
+            format_bytes(heap.peak_bytes),
+            heap.total_allocations,
+            format_bytes(heap.total_bytes)
+        ),
+        None => "-".into(),
+    }
+}
+
+/// Replace the table of a year. If the year has no table yet, it is added after the table of another year.
+fn update_content(
+    s: &mut String,
+    year: Year,
+    timings: Timings,
+    total_millis: f64,
+) -> Result<(), Error> {
+    let table = construct_table("##", year, timings, total_millis);
+
+    let marker = get_marker(year);
+
+    if s.contains(&marker) {
+        let positions = locate_table(s, &marker)?;
+        s.replace_range(positions.pos_start..positions.pos_end, &table);
+    } else {
+        let pos = s
+            .rfind(MARKER_PREFIX)
+            .and_then(|start| s[start..].find("--->").map(|end| start + end + 4))
+            .ok_or_else(|| Error::Parser("Could not find a table to add the year to.".into()))?;
+        s.insert_str(pos, &format!("\n\n{table}"));
+    }
+
     Ok(())
 }
 
-pub fn update(timings: Timings) -> Result<(), Error> {
+pub fn update(year: Year, timings: Timings) -> Result<(), Error> {
     let path = "README.md";
     let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
     let total_millis = timings.total_millis();
-    update_content(&mut readme, timings, total_millis)?;
+    update_content(&mut readme, year, timings, total_millis)?;
     fs::write(path, &readme)?;
     Ok(())
 }
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{MARKER, update_content};
-    use crate::{day, template::timings::Timing, template::timings::Timings};
+    use super::update_content;
+    use crate::{
+        day,
+        template::timings::{HeapStatistics, Timing, Timings},
+        year,
+    };
+
+    const MARKER: &str = "<!--- benchmarking table 2025 --->";
 
     fn get_mock_timings() -> Timings {
         Timings {
@@ -109,18 +165,30 @@ mod tests {
                     day: day!(1),
                     part_1: Some("10ms".into()),
                     part_2: Some("20ms".into()),
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    part_1_heap: None,
+                    part_2_heap: None,
                     total_nanos: 3e+10,
                 },
                 Timing {
                     day: day!(2),
                     part_1: Some("30ms".into()),
                     part_2: Some("40ms".into()),
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    part_1_heap: None,
+                    part_2_heap: None,
                     total_nanos: 7e+10,
                 },
                 Timing {
                     day: day!(4),
                     part_1: Some("40ms".into()),
                     part_2: Some("50ms".into()),
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    part_1_heap: None,
+                    part_2_heap: None,
                     total_nanos: 9e+10,
                 },
             ],
@@ -131,53 +199,89 @@ mod tests {
     #[should_panic]
     fn errors_if_marker_not_present() {
         let mut s = "# readme".to_string();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, year!(2025), get_mock_timings(), 190.0).unwrap();
     }
 
     #[test]
     #[should_panic]
     fn errors_if_too_many_markers_present() {
         let mut s = format!("{} {} {}", MARKER, MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, year!(2025), get_mock_timings(), 190.0).unwrap();
     }
 
     #[test]
     fn updates_empty_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        assert_eq!(s.contains("## Benchmarks"), true);
+        update_content(&mut s, year!(2025), get_mock_timings(), 190.0).unwrap();
+        assert_eq!(s.contains("## 2025 Benchmarks"), true);
     }
 
     #[test]
     fn updates_existing_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, year!(2025), get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, year!(2025), get_mock_timings(), 190.0).unwrap();
         assert_eq!(s.matches(MARKER).collect::<Vec<&str>>().len(), 2);
-        assert_eq!(s.matches("## Benchmarks").collect::<Vec<&str>>().len(), 1);
+        assert_eq!(
+            s.matches("## 2025 Benchmarks").collect::<Vec<&str>>().len(),
+            1
+        );
     }
 
     #[test]
     fn format_benchmarks() {
         let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, year!(2025), get_mock_timings(), 190.0).unwrap();
         let expected = [
             "foo",
             "bar",
-            "<!--- benchmarking table --->",
-            "## Benchmarks",
+            "<!--- benchmarking table 2025 --->",
+            "## 2025 Benchmarks",
             "",
             "| Day | Part 1 | Part 2 |",
             "| :---: | :---: | :---:  |",
-            "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` |",
-            "| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` |",
-            "| [Day 4](./src/bin/04.rs) | `40ms` | `50ms` |",
+            "| [Day 1](./src/bin/2025/01.rs) | `10ms` | `20ms` |",
+            "| [Day 2](./src/bin/2025/02.rs) | `30ms` | `40ms` |",
+            "| [Day 4](./src/bin/2025/04.rs) | `40ms` | `50ms` |",
             "",
             "**Total: 190.00ms**",
-            "<!--- benchmarking table --->",
+            "<!--- benchmarking table 2025 --->",
             "baz",
         ]
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    #[test]
+    fn format_benchmarks_with_heap_statistics() {
+        let mut timings = get_mock_timings();
+        timings.data[0].part_1_heap = Some(HeapStatistics {
+            peak_bytes: 232,
+            total_allocations: 3,
+            total_bytes: 2048,
+        });
+
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        update_content(&mut s, year!(2025), timings, 190.0).unwrap();
+
+        assert!(s.contains("| Day | Part 1 | Part 2 | Part 1 (heap) | Part 2 (heap) |"));
+        assert!(s.contains(
+            "| [Day 1](./src/bin/2025/01.rs) | `10ms` | `20ms` | `232 B` peak · 3 allocs · `2.0 KiB` total | - |"
+        ));
+        assert!(s.contains("| [Day 2](./src/bin/2025/02.rs) | `30ms` | `40ms` | - | - |"));
+    }
+
+    #[test]
+    fn adds_tables_for_new_years() {
+        let mut s = format!("foo\n{}{}\nbaz", MARKER, MARKER);
+        update_content(&mut s, year!(2024), get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, year!(2024), get_mock_timings(), 190.0).unwrap();
+
+        let pos_2025 = s.rfind(MARKER).unwrap();
+        let pos_2024 = s.find("<!--- benchmarking table 2024 --->").unwrap();
+        assert!(pos_2025 < pos_2024);
+        assert_eq!(s.matches("## 2024 Benchmarks").count(), 1);
+        assert!(s.contains("| [Day 1](./src/bin/2024/01.rs) | `10ms` | `20ms` |"));
+        assert!(s.ends_with("<!--- benchmarking table 2024 --->\nbaz"));
+    }
 }
@@ -0,0 +1,130 @@
+/// Module with a small, seedable random number generator for input generators.
+use std::ops::RangeInclusive;
+
+/// A `SplitMix64` pseudo random number generator.
+/// Not suitable for cryptography, but fast and deterministic across platforms, so that
+/// the same seed always generates the same input.
+///
+/// ```
+/// # use advent_of_code::template::Rng;
+/// let mut rng = Rng::new(42);
+/// let roll = rng.range(1..=6);
+/// assert!((1..=6).contains(&roll));
+/// assert_eq!(Rng::new(42).range(1..=6), roll);
+/// ```
+#[derive(Clone, Debug)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// A uniformly distributed number in an inclusive range.
+    ///
+    /// # Panics
+    /// Panics if the range is empty.
+    pub fn range(&mut self, range: RangeInclusive<u64>) -> u64 {
+        let (start, end) = range.into_inner();
+        assert!(start <= end, "expected a non-empty range.");
+
+        // NOTE: multiply-shift instead of a modulo, its bias is negligible for the ranges of puzzle inputs.
+        match (end - start).checked_add(1) {
+            #[allow(clippy::cast_possible_truncation)]
+            Some(len) => start + ((u128::from(self.next_u64()) * u128::from(len)) >> 64) as u64,
+            None => self.next_u64(),
+        }
+    }
+
+    /// A uniformly distributed index into a collection of `len` items.
+    ///
+    /// # Panics
+    /// Panics if `len` is zero.
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn index(&mut self, len: usize) -> usize {
+        assert!(len > 0, "expected at least one item.");
+        self.range(0..=(len as u64 - 1)) as usize
+    }
+
+    /// `true` with a probability of `p`.
+    pub fn chance(&mut self, p: f64) -> bool {
+        #[allow(clippy::cast_precision_loss)]
+        let x = (self.next_u64() >> 11) as f64 / (1_u64 << 53) as f64;
+        x < p
+    }
+
+    /// A random item of a slice.
+    ///
+    /// # Panics
+    /// Panics if the slice is empty.
+    pub fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.index(items.len())]
+    }
+
+    /// Shuffle a slice in place (Fisher-Yates).
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            let j = self.index(i + 1);
+            items.swap(i, j);
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Rng;
+
+    #[test]
+    fn is_deterministic() {
+        let a: Vec<u64> = (0..8)
+            .scan(Rng::new(7), |r, _| Some(r.next_u64()))
+            .collect();
+        let b: Vec<u64> = (0..8)
+            .scan(Rng::new(7), |r, _| Some(r.next_u64()))
+            .collect();
+        let c: Vec<u64> = (0..8)
+            .scan(Rng::new(8), |r, _| Some(r.next_u64()))
+            .collect();
+        assert_eq!(a, b);
+        assert_ne!(a, c);
+    }
+
+    #[test]
+    fn stays_in_range() {
+        let mut rng = Rng::new(1);
+        let mut seen = [false; 6];
+
+        for _ in 0..1000 {
+            let x = rng.range(3..=8);
+            assert!((3..=8).contains(&x));
+            seen[(x - 3) as usize] = true;
+        }
+
+        assert!(seen.iter().all(|x| *x));
+        assert_eq!(rng.range(5..=5), 5);
+        rng.range(0..=u64::MAX);
+    }
+
+    #[test]
+    fn shuffles_permutations() {
+        let mut rng = Rng::new(3);
+        let mut items: Vec<u32> = (0..50).collect();
+        rng.shuffle(&mut items);
+
+        assert_ne!(items, (0..50).collect::<Vec<_>>());
+        items.sort_unstable();
+        assert_eq!(items, (0..50).collect::<Vec<_>>());
+    }
+}
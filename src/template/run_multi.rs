@@ -1,40 +1,169 @@
-use std::{collections::HashSet, io};
+use std::{
+    collections::{BTreeMap, HashSet},
+    io,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc,
+    },
+    thread,
+};
 
-use crate::template::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET, Day};
+use crate::template::{
+    ANSI_BOLD, ANSI_ITALIC, ANSI_RESET, Day, Puzzle, Solution, Year,
+    runner::{PartResult, format_part_result},
+};
 
 use super::{
     all_days,
+    answers::{Answers, Verification},
     timings::{Timing, Timings},
 };
 
-pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -> Option<Timings> {
+/// The outcome of [`run_multi`].
+pub struct Summary {
+    /// Timings of all solved days, if they were benched.
+    pub timings: Option<Timings>,
+    /// Number of parts that did not produce their known answer.
+    pub failed_verifications: usize,
+}
+
+/// The cargo profile that solutions are run with.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Profile {
+    Debug,
+    Release,
+    /// Release build with the `dhat-heap` feature, which measures the heap usage of every part.
+    Dhat,
+}
+
+impl Profile {
+    /// Arguments that select this profile when passed to `cargo run`.
+    /// Solutions are built without default features, so that they do not compile and link the HTTP client.
+    pub fn cargo_args(self) -> &'static [&'static str] {
+        match self {
+            Profile::Debug => &["--no-default-features"],
+            Profile::Release => &["--release", "--no-default-features"],
+            Profile::Dhat => &[
+                "--profile",
+                "dhat",
+                "--no-default-features",
+                "--features",
+                "dhat-heap",
+            ],
+        }
+    }
+
+    /// Whether the main binary was built with this profile, so that linked solutions can run in-process.
+    fn is_current(self) -> bool {
+        match self {
+            Profile::Debug => cfg!(debug_assertions),
+            Profile::Release => !cfg!(debug_assertions),
+            // NOTE: solutions are not linked into the main binary when profiling with DHAT.
+            Profile::Dhat => false,
+        }
+    }
+}
+
+/// Run a set of days of a year. Days with a linked [`Solution`] run in-process if the main binary was built
+/// with the requested profile, all other days are run by spawning their binary with `cargo`.
+///
+/// With more than one job, days run concurrently. Their output is buffered and printed in order of days.
+/// If `answers` are passed, every part result is marked with whether it matches its known answer.
+pub fn run_multi(
+    year: Year,
+    days_to_run: &HashSet<Day>,
+    profile: Profile,
+    is_timed: bool,
+    jobs: usize,
+    solutions: &[Solution],
+    answers: Option<&Answers>,
+) -> Summary {
     let mut timings: Vec<Timing> = Vec::with_capacity(days_to_run.len());
+    let mut failed_verifications = 0;
 
-    let mut need_space = false;
+    let is_in_process = profile.is_current();
 
     // NOTE: use non-duplicate, sorted day values.
-    all_days()
+    let days: Vec<Day> = all_days(year)
         .filter(|day| days_to_run.contains(day))
-        .for_each(|day| {
-            if need_space {
-                println!();
+        .collect();
+
+    let format = |result: &PartResult| {
+        let formatted = format_part_result(result);
+
+        match answers.map(|a| a.verify(result)) {
+            Some(verification) => with_verification(formatted, verification),
+            None => formatted,
+        }
+    };
+
+    let run_day = |day: Day, out: &mut dyn FnMut(String)| {
+        out(format!("{ANSI_BOLD}Day {day}{ANSI_RESET}"));
+        out("------".into());
+
+        let puzzle = Puzzle::new(year, day);
+        let solution = solutions.iter().find(|s| s.puzzle == puzzle);
+
+        let results = match solution {
+            Some(solution) if is_in_process => {
+                in_process::run_solution(solution, is_timed, &format, out)
             }
-            need_space = true;
+            _ => child_commands::run_solution(puzzle, is_timed, profile, &format, out).unwrap(),
+        };
 
-            println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
-            println!("------");
+        if results.is_empty() {
+            out("Not solved.".into());
 
-            let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+            if answers.is_some_and(|a| a.has_day(day)) {
+                out("✗ answers are known for this day, but the solution did not run.".into());
+            }
+        }
+
+        results
+    };
 
-            if output.is_empty() {
-                println!("Not solved.");
+    let mut need_space = false;
+
+    let mut on_day_done = |day: Day, results: Vec<PartResult>| {
+        if let Some(answers) = answers {
+            failed_verifications += if results.is_empty() {
+                usize::from(answers.has_day(day))
             } else {
-                let val = child_commands::parse_exec_time(&output, day);
-                timings.push(val);
+                results
+                    .iter()
+                    .filter(|r| matches!(answers.verify(r), Verification::Mismatch(_)))
+                    .count()
+            };
+        }
+
+        if !results.is_empty() {
+            timings.push(child_commands::to_timing(&results, day));
+        }
+    };
+
+    if jobs > 1 {
+        run_parallel(&days, jobs, run_day, |day, lines, results| {
+            if need_space {
+                println!();
             }
+            need_space = true;
+
+            lines.iter().for_each(|line| println!("{line}"));
+            on_day_done(day, results);
         });
+    } else {
+        for day in days {
+            if need_space {
+                println!();
+            }
+            need_space = true;
+
+            let results = run_day(day, &mut |line| println!("{line}"));
+            on_day_done(day, results);
+        }
+    }
 
-    if is_timed {
+    let timings = if is_timed {
         let timings = Timings { data: timings };
         let total_millis = timings.total_millis();
         println!(
@@ -43,6 +172,107 @@ pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -
         Some(timings)
     } else {
         None
+    };
+
+    Summary {
+        timings,
+        failed_verifications,
+    }
+}
+
+/// Append a ✓ or ✗ to the first line of a formatted part result.
+fn with_verification(formatted: String, verification: Verification) -> String {
+    let mark = match verification {
+        Verification::Match => " ✓".to_string(),
+        Verification::Mismatch(expected) => format!(" ✗ (expected {expected})"),
+        Verification::Unknown => return formatted,
+    };
+
+    match formatted.split_once('\n') {
+        Some((first_line, rest)) => format!("{first_line}{mark}\n{rest}"),
+        None => formatted + &mark,
+    }
+}
+
+/// Run days on a pool of `jobs` threads, buffering the output of each day.
+/// `on_done` is called on the calling thread in order of `days`, as soon as all previous days have finished.
+fn run_parallel(
+    days: &[Day],
+    jobs: usize,
+    run_day: impl Fn(Day, &mut dyn FnMut(String)) -> Vec<PartResult> + Sync,
+    mut on_done: impl FnMut(Day, Vec<String>, Vec<PartResult>),
+) {
+    let next_day = AtomicUsize::new(0);
+    let (tx, rx) = mpsc::channel();
+
+    thread::scope(|scope| {
+        for _ in 0..jobs.min(days.len()) {
+            let tx = tx.clone();
+            let next_day = &next_day;
+            let run_day = &run_day;
+
+            scope.spawn(move || {
+                while let Some(&day) = days.get(next_day.fetch_add(1, Ordering::Relaxed)) {
+                    let mut lines = vec![];
+                    let results = run_day(day, &mut |line| lines.push(line));
+
+                    if tx.send((day, lines, results)).is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+
+        // NOTE: drop the original sender so that the receiver stops once all workers are done.
+        drop(tx);
+
+        let mut pending = BTreeMap::new();
+        let mut next_to_print = days.iter();
+        let mut expected = next_to_print.next();
+
+        for (day, lines, results) in rx {
+            pending.insert(day, (lines, results));
+
+            while let Some(day) = expected {
+                let Some((lines, results)) = pending.remove(day) else {
+                    break;
+                };
+                on_done(*day, lines, results);
+                expected = next_to_print.next();
+            }
+        }
+    });
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{thread, time::Duration};
+
+    use super::run_parallel;
+    use crate::{template::all_days, year};
+
+    #[test]
+    fn runs_days_in_parallel_and_reports_in_order() {
+        let days: Vec<_> = all_days(year!(2025)).take(8).collect();
+        let mut reported = vec![];
+
+        run_parallel(
+            &days,
+            4,
+            |day, out| {
+                // NOTE: later days finish first.
+                thread::sleep(Duration::from_millis(u64::from(10 - day.into_inner()) * 5));
+                out(format!("Day {day}"));
+                vec![]
+            },
+            |day, lines, _| reported.push((day, lines)),
+        );
+
+        assert_eq!(reported.len(), 8);
+        for ((day, lines), expected) in reported.iter().zip(&days) {
+            assert_eq!(day, expected);
+            assert_eq!(lines, &vec![format!("Day {expected}")]);
+        }
     }
 }
 
@@ -59,16 +289,40 @@ impl From<std::io::Error> for Error {
     }
 }
 
-#[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+/// Solutions that are linked into the main binary can be run without invoking `cargo`.
+pub mod in_process {
+    use std::panic;
+
+    use crate::template::{Solution, runner::PartResult, try_read_input};
+
+    /// Run a linked solution against its input and write its formatted results to `out`.
+    /// Mirrors a failing child command: if the input can not be used or the solution panics, no results are returned.
+    pub fn run_solution(
+        solution: &Solution,
+        is_timed: bool,
+        format: &dyn Fn(&PartResult) -> String,
+        out: &mut dyn FnMut(String),
+    ) -> Vec<PartResult> {
+        let input = match try_read_input(solution.puzzle) {
+            Ok(input) => input,
+            Err(e) => {
+                out(e);
+                return vec![];
+            }
+        };
+
+        let results = panic::catch_unwind(|| (solution.run)(&input, is_timed)).unwrap_or_default();
+
+        results.iter().map(format).for_each(out);
+        results
+    }
 }
 
 /// All solutions live in isolated binaries.
-/// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
+/// This module encapsulates interaction with these binaries, both invoking them as well as reading their results.
 pub mod child_commands {
-    use super::{Error, get_path_for_bin};
-    use crate::template::Day;
+    use super::{Error, Profile};
+    use crate::template::{Day, Puzzle, runner::PartResult, timings::Timing};
     use std::{
         io::{BufRead, BufReader},
         path::Path,
@@ -76,28 +330,33 @@ pub mod child_commands {
         thread,
     };
 
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
+    /// Run the solution bin for a given puzzle in JSON output mode.
+    /// Part results are formatted and written to `out` as they arrive, any other output of the solution is forwarded as-is.
+    pub fn run_solution(
+        puzzle: Puzzle,
+        is_timed: bool,
+        profile: Profile,
+        format: &dyn Fn(&PartResult) -> String,
+        out: &mut dyn FnMut(String),
+    ) -> Result<Vec<PartResult>, Error> {
         // skip command invocation for days that have not been scaffolded yet.
-        if !Path::new(&get_path_for_bin(day)).exists() {
+        if !Path::new(&puzzle.bin_path()).exists() {
             return Ok(vec![]);
         }
 
-        let day_padded = day.to_string();
-        let mut args = vec!["run", "--quiet", "--bin", &day_padded];
+        let bin_name = puzzle.to_string();
+        let mut args = vec!["run", "--quiet", "--bin", &bin_name];
+        args.extend(profile.cargo_args());
 
-        if is_release {
-            args.push("--release");
-        }
+        args.extend(["--", "--format", "json"]);
 
         if is_timed {
             // mirror `--time` flag to child invocations.
-            args.push("--");
             args.push("--time");
         }
 
         // spawn child command with piped stdout/stderr.
-        // forward output to stdout/stderr while grabbing stdout lines.
+        // forward output to stdout/stderr while grabbing part results.
 
         let mut cmd = Command::new("cargo")
             .args(&args)
@@ -108,7 +367,7 @@ pub mod child_commands {
         let stdout = BufReader::new(cmd.stdout.take().ok_or(super::Error::BrokenPipe)?);
         let stderr = BufReader::new(cmd.stderr.take().ok_or(super::Error::BrokenPipe)?);
 
-        let mut output = vec![];
+        let mut results = vec![];
 
         let thread = thread::spawn(move || {
             stderr.lines().for_each(|line| {
@@ -118,75 +377,55 @@ pub mod child_commands {
 
         for line in stdout.lines() {
             let line = line.unwrap();
-            println!("{line}");
-            output.push(line);
+            match PartResult::try_from(line.as_str()) {
+                Ok(result) => {
+                    out(format(&result));
+                    results.push(result);
+                }
+                Err(_) => out(line),
+            }
         }
 
         thread.join().unwrap();
         cmd.wait()?;
 
-        Ok(output)
+        Ok(results)
     }
 
-    pub fn parse_exec_time(output: &[String], day: Day) -> super::Timing {
-        let mut timings = super::Timing {
+    /// Collect the results of solved parts into a [`Timing`] for the given day.
+    pub fn to_timing(results: &[PartResult], day: Day) -> Timing {
+        let mut timing = Timing {
             day,
             part_1: None,
             part_2: None,
+            part_1_stats: None,
+            part_2_stats: None,
+            part_1_heap: None,
+            part_2_heap: None,
             total_nanos: 0_f64,
         };
 
-        output
-            .iter()
-            .filter_map(|l| {
-                if !l.contains(" samples)") {
-                    return None;
-                }
-
-                let Some((timing_str, nanos)) = parse_time(l) else {
-                    eprintln!("Could not parse timings from line: {l}");
-                    return None;
-                };
+        for result in results.iter().filter(|r| r.answer.is_some()) {
+            let duration_str = format!("{:.1?}", result.duration());
 
-                let part = l.split(':').next()?;
-                Some((part, timing_str, nanos))
-            })
-            .for_each(|(part, timing_str, nanos)| {
-                if part.contains("Part 1") {
-                    timings.part_1 = Some(timing_str.into());
-                } else if part.contains("Part 2") {
-                    timings.part_2 = Some(timing_str.into());
+            match result.part {
+                1 => {
+                    timing.part_1 = Some(duration_str);
+                    timing.part_1_stats.clone_from(&result.statistics);
+                    timing.part_1_heap.clone_from(&result.heap);
                 }
+                2 => {
+                    timing.part_2 = Some(duration_str);
+                    timing.part_2_stats.clone_from(&result.statistics);
+                    timing.part_2_heap.clone_from(&result.heap);
+                }
+                _ => continue,
+            }
 
-                timings.total_nanos += nanos;
-            });
-
-        timings
-    }
-
-    fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
-        s.split(postfix).next()?.parse().ok()
-    }
+            timing.total_nanos += result.duration_nanos;
+        }
 
-    fn parse_time(line: &str) -> Option<(&str, f64)> {
-        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
-        let str_timing = line
-            .split(" samples)")
-            .next()?
-            .split('(')
-            .next_back()?
-            .split('@')
-            .next()?
-            .trim();
-
-        let parsed_timing = match str_timing {
-            s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
-            s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
-            s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
-            s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
-
-        Some((str_timing, parsed_timing))
+        timing
     }
 
     /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
@@ -205,53 +444,80 @@ pub mod child_commands {
 
     #[cfg(feature = "test_lib")]
     mod tests {
-        use super::parse_exec_time;
+        use super::to_timing;
+
+        use crate::{day, template::runner::PartResult};
 
-        use crate::day;
+        fn parse(lines: &[&str]) -> Vec<PartResult> {
+            lines
+                .iter()
+                .filter_map(|l| PartResult::try_from(*l).ok())
+                .collect()
+        }
 
         #[test]
         fn parses_execution_times() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: 0 (74.13ns @ 100000 samples)".into(),
-                    "Part 2: 10 (74.13ms @ 99999 samples)".into(),
-                    "".into(),
-                ],
+            let res = to_timing(
+                &parse(&[
+                    r#"{"day":"01","part":1,"answer":"0","duration_nanos":74.13,"samples":100000,"statistics":null}"#,
+                    r#"{"day":"01","part":2,"answer":"10","duration_nanos":74130000,"samples":99999,"statistics":null}"#,
+                    "",
+                ]),
                 day!(1),
             );
             assert_approx_eq!(res.total_nanos, 74130074.13_f64);
-            assert_eq!(res.part_1.unwrap(), "74.13ns");
-            assert_eq!(res.part_2.unwrap(), "74.13ms");
+            assert_eq!(res.part_1.unwrap(), "74.0ns");
+            assert_eq!(res.part_2.unwrap(), "74.1ms");
         }
 
         #[test]
         fn parses_with_patterns_in_input() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: @ @ @ ( ) ms (2s @ 5 samples)".into(),
-                    "Part 2: 10s (100ms @ 1 samples)".into(),
-                    "".into(),
-                ],
+            let res = to_timing(
+                &parse(&[
+                    r#"{"day":"01","part":1,"answer":"@ @ @ ( ) ms (1s @ 5 samples)","duration_nanos":2000000000,"samples":5,"statistics":null}"#,
+                    "Part 2: 10s (100ms @ 1 samples)",
+                    r#"{"day":"01","part":2,"answer":"10s","duration_nanos":100000000,"samples":1,"statistics":null}"#,
+                    "",
+                ]),
                 day!(1),
             );
             assert_approx_eq!(res.total_nanos, 2100000000_f64);
-            assert_eq!(res.part_1.unwrap(), "2s");
-            assert_eq!(res.part_2.unwrap(), "100ms");
+            assert_eq!(res.part_1.unwrap(), "2.0s");
+            assert_eq!(res.part_2.unwrap(), "100.0ms");
         }
 
         #[test]
         fn parses_missing_parts() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: ✖        ".into(),
-                    "Part 2: ✖        ".into(),
-                    "".into(),
-                ],
+            let res = to_timing(
+                &parse(&[
+                    r#"{"day":"01","part":1,"answer":null,"duration_nanos":10,"samples":1,"statistics":null}"#,
+                    r#"{"day":"01","part":2,"answer":null,"duration_nanos":10,"samples":1,"statistics":null}"#,
+                    "",
+                ]),
                 day!(1),
             );
             assert_approx_eq!(res.total_nanos, 0_f64);
             assert_eq!(res.part_1.is_none(), true);
             assert_eq!(res.part_2.is_none(), true);
         }
+
+        #[test]
+        fn parses_statistics() {
+            let res = to_timing(
+                &parse(&[
+                    r#"{"day":"01","part":1,"answer":"0","duration_nanos":74,"samples":100000,"statistics":{"mean":74,"min":70.1,"median":72,"p95":80.2,"max":1200,"std_dev":3.1}}"#,
+                    r#"{"day":"01","part":2,"answer":"10","duration_nanos":74130000,"samples":1,"statistics":null}"#,
+                ]),
+                day!(1),
+            );
+            let stats = res.part_1_stats.unwrap();
+            assert_approx_eq!(stats.mean, 74_f64);
+            assert_approx_eq!(stats.min, 70.1_f64);
+            assert_approx_eq!(stats.median, 72.0_f64);
+            assert_approx_eq!(stats.p95, 80.2_f64);
+            assert_approx_eq!(stats.max, 1200_f64);
+            assert_approx_eq!(stats.std_dev, 3.1_f64);
+            assert_eq!(res.part_2_stats.is_none(), true);
+        }
     }
 }
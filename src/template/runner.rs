@@ -1,63 +1,236 @@
 /// Encapsulates code that interacts with solution functions.
+use std::collections::HashMap;
 use std::fmt::Display;
 use std::hint::black_box;
 use std::io::{Write, stdout};
-use std::process::Output;
+use std::str::FromStr;
 use std::time::{Duration, Instant};
 use std::{cmp, env, process};
 
+use tinyjson::JsonValue;
+
 use crate::template::ANSI_BOLD;
-use crate::template::{ANSI_ITALIC, ANSI_RESET, Day, aoc_cli};
+use crate::template::differential::{self, Part};
+use crate::template::scaling::{self, fit};
+use crate::template::timings::{HeapStatistics, Statistics, format_bytes};
+use crate::template::{
+    ANSI_ITALIC, ANSI_RESET, Day, Generator, Puzzle, Visualization, Visualizer, try_read_input,
+};
+#[cfg(feature = "client")]
+use crate::template::{
+    answers::Answers,
+    aoc_client,
+    submissions::{Submissions, SubmitOutcome},
+};
+
+/// The format in which part results are written to stdout, selected with `--format <text|json>`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// Human-readable output, decorated with ANSI escape codes.
+    Text,
+    /// One JSON object per part and line, see [`PartResult`].
+    Json,
+}
+
+impl OutputFormat {
+    fn from_args() -> Self {
+        let args: Vec<String> = env::args().collect();
+
+        let Some(index) = args.iter().position(|x| x == "--format") else {
+            return OutputFormat::Text;
+        };
+
+        match args.get(index + 1).map(String::as_str) {
+            Some("text") => OutputFormat::Text,
+            Some("json") => OutputFormat::Json,
+            _ => {
+                eprintln!("Unexpected command-line input. Format: --format <text|json>");
+                process::exit(1);
+            }
+        }
+    }
+}
+
+/// The outcome of running one part of a solution.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PartResult {
+    pub day: Day,
+    pub part: u8,
+    pub answer: Option<String>,
+    pub duration_nanos: f64,
+    pub samples: u128,
+    pub statistics: Option<Statistics>,
+    /// Heap usage of the first run, only measured when built with the `dhat-heap` feature.
+    pub heap: Option<HeapStatistics>,
+}
 
-pub fn run_part<I: Copy, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
+impl PartResult {
+    /// The (mean) duration of the part as a [`Duration`].
+    pub fn duration(&self) -> Duration {
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        Duration::from_nanos(self.duration_nanos.round() as u64)
+    }
+}
+
+pub fn run_part<I: Copy, T: Display>(
+    func: impl Fn(I) -> Option<T>,
+    input: I,
+    puzzle: Puzzle,
+    part: u8,
+) {
+    let format = OutputFormat::from_args();
+    let is_timed = env::args().any(|x| x == "--time");
     let part_str = format!("Part {part}");
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+    let measurement = run_timed(func, input, is_timed, |result| {
+        if format == OutputFormat::Text {
+            print_result(result, &part_str, "");
+
+            if is_timed {
+                print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
+                let _ = stdout().flush();
+            }
+        }
+    });
+
+    match format {
+        OutputFormat::Text => {
+            print_result(
+                &measurement.result,
+                &part_str,
+                &format_duration(&measurement.duration, measurement.samples),
+            );
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+            if let Some(statistics) = &measurement.statistics {
+                println!("{}", format_statistics(statistics));
+            }
 
-    if let Some(result) = result {
-        submit_result(result, day, part);
+            if let Some(heap) = &measurement.heap {
+                println!("{}", format_heap(heap));
+            }
+        }
+        OutputFormat::Json => {
+            let part_result = to_part_result(&measurement, puzzle.day, part);
+            println!("{}", JsonValue::from(&part_result).stringify().unwrap());
+        }
+    }
+
+    if let Some(result) = measurement.result {
+        submit_result(result, puzzle, part);
     }
 }
 
+/// Run a solution part without printing anything and return its [`PartResult`].
+/// Used to run solutions in-process, see [`crate::template::Solution`].
+pub fn measure_part<I: Copy, T: Display>(
+    func: impl Fn(I) -> Option<T>,
+    input: I,
+    day: Day,
+    part: u8,
+    is_timed: bool,
+) -> PartResult {
+    let measurement = run_timed(func, input, is_timed, |_| {});
+    to_part_result(&measurement, day, part)
+}
+
+fn to_part_result<T: Display>(
+    measurement: &Measurement<Option<T>>,
+    day: Day,
+    part: u8,
+) -> PartResult {
+    PartResult {
+        day,
+        part,
+        answer: measurement.result.as_ref().map(ToString::to_string),
+        #[allow(clippy::cast_precision_loss)]
+        duration_nanos: measurement.duration.as_nanos() as f64,
+        samples: measurement.samples,
+        statistics: measurement.statistics.clone(),
+        heap: measurement.heap.clone(),
+    }
+}
+
+/// The result of a solution part, along with how long it took and how much memory it used.
+struct Measurement<T> {
+    result: T,
+    duration: Duration,
+    samples: u128,
+    statistics: Option<Statistics>,
+    heap: Option<HeapStatistics>,
+}
+
 /// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
 ///  1. in debug, the function is executed once.
 ///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
+///
+/// Benched runs additionally return the [`Statistics`] of all measured samples.
+/// When built with the `dhat-heap` feature, the heap usage of the first execution is measured as well.
+/// `hook` is called with the result of the first execution, before benching starts.
 fn run_timed<I: Copy, T>(
     func: impl Fn(I) -> T,
     input: I,
+    is_timed: bool,
     hook: impl Fn(&T),
-) -> (T, Duration, u128) {
+) -> Measurement<T> {
     let timer = Instant::now();
-    let result = {
+    let (result, heap) = {
         #[cfg(feature = "dhat-heap")]
         let _profiler = dhat::Profiler::new_heap();
 
-        func(input)
+        let result = func(input);
+
+        #[cfg(feature = "dhat-heap")]
+        let heap = Some(HeapStatistics::from(dhat::HeapStats::get()));
+        #[cfg(not(feature = "dhat-heap"))]
+        let heap = None;
+
+        (result, heap)
     };
     let base_time = timer.elapsed();
 
     hook(&result);
 
-    let run = if std::env::args().any(|x| x == "--time") {
-        bench(func, input, &base_time)
+    if is_timed {
+        let (statistics, samples) = bench(func, input, &base_time);
+        Measurement {
+            result,
+            duration: statistics.mean_duration(),
+            samples,
+            statistics: Some(statistics),
+            heap,
+        }
     } else {
-        (base_time, 1)
-    };
-
-    (result, run.0, run.1)
+        Measurement {
+            result,
+            duration: base_time,
+            samples: 1,
+            statistics: None,
+            heap,
+        }
+    }
 }
 
-fn bench<I: Copy, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
-    let mut stdout = stdout();
-
-    print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
-    let _ = stdout.flush();
+#[cfg(feature = "dhat-heap")]
+impl From<dhat::HeapStats> for HeapStatistics {
+    fn from(value: dhat::HeapStats) -> Self {
+        HeapStatistics {
+            peak_bytes: value.max_bytes as u64,
+            total_allocations: value.total_blocks,
+            total_bytes: value.total_bytes,
+        }
+    }
+}
 
+/// Bench a solution part. A tenth of the iterations (at least one) is run as a warm-up first;
+/// these samples are discarded so that cold caches and lazy allocations do not skew the results.
+fn bench<I: Copy, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Statistics, u128) {
     let bench_iterations =
         (Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10)).clamp(10, 10000);
+    let warmup_iterations = cmp::max(bench_iterations / 10, 1);
+
+    for _ in 0..warmup_iterations {
+        black_box(func(black_box(input)));
+    }
 
     let mut timers: Vec<Duration> = vec![];
 
@@ -67,19 +240,7 @@ fn bench<I: Copy, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (
         timers.push(timer.elapsed());
     }
 
-    (
-        #[allow(clippy::cast_possible_truncation)]
-        Duration::from_nanos(average_duration(&timers) as u64),
-        bench_iterations,
-    )
-}
-
-fn average_duration(numbers: &[Duration]) -> u128 {
-    numbers
-        .iter()
-        .map(std::time::Duration::as_nanos)
-        .sum::<u128>()
-        / numbers.len() as u128
+    (Statistics::from_samples(&timers), bench_iterations)
 }
 
 fn format_duration(duration: &Duration, samples: u128) -> String {
@@ -90,6 +251,57 @@ fn format_duration(duration: &Duration, samples: u128) -> String {
     }
 }
 
+/// Format a part result the same way [`run_part`] prints it in text mode.
+pub fn format_part_result(result: &PartResult) -> String {
+    let part = format!("Part {}", result.part);
+    let duration_str = format_duration(&result.duration(), result.samples);
+
+    let mut str = match &result.answer {
+        Some(answer) if answer.contains('\n') => format!("{part}: ▼ {duration_str}\n{answer}"),
+        Some(answer) => format!("{part}: {ANSI_BOLD}{answer}{ANSI_RESET}{duration_str}"),
+        None => format!("{part}: ✖"),
+    };
+
+    if let Some(statistics) = &result.statistics {
+        str.push('\n');
+        str.push_str(&format_statistics(statistics));
+    }
+
+    if let Some(heap) = &result.heap {
+        str.push('\n');
+        str.push_str(&format_heap(heap));
+    }
+
+    str
+}
+
+/// Formats the spread of a benched part, e.g. `  ↳ min 70.1ns · median 72.0ns · p95 80.2ns · max 100.3ns · σ 3.1ns`.
+fn format_statistics(statistics: &Statistics) -> String {
+    let nanos = |x: f64| {
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        Duration::from_nanos(x.round() as u64)
+    };
+
+    format!(
+        "  ↳ min {:.1?} · median {:.1?} · p95 {:.1?} · max {:.1?} · σ {:.1?}",
+        nanos(statistics.min),
+        nanos(statistics.median),
+        nanos(statistics.p95),
+        nanos(statistics.max),
+        nanos(statistics.std_dev),
+    )
+}
+
+/// Formats the heap usage of a part, e.g. `  ↳ heap: peak 232 B · 3 allocations · 276 B total`.
+fn format_heap(heap: &HeapStatistics) -> String {
+    format!(
+        "  ↳ heap: peak {} · {} allocations · {} total",
+        format_bytes(heap.peak_bytes),
+        heap.total_allocations,
+        format_bytes(heap.total_bytes),
+    )
+}
+
 fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
     let is_intermediate_result = duration_str.is_empty();
 
@@ -125,14 +337,320 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
     }
 }
 
+/// A named implementation of a part, registered with the `solution!` macro.
+pub type Variant<T> = (&'static str, fn(&str) -> Option<T>);
+
+/// A benched variant of a part, see [`compare_variants`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct VariantResult {
+    pub name: String,
+    pub answer: Option<String>,
+    pub statistics: Statistics,
+}
+
+/// Bench alternative implementations of a part against the same input and print them side by side,
+/// relative to the first variant. Returns whether all variants returned the same answer.
+pub fn compare_variants<T: Display>(input: &str, variants: &[Variant<T>]) -> bool {
+    let mut results = vec![];
+
+    for (name, func) in variants {
+        print!("{name} > {ANSI_ITALIC}benching{ANSI_RESET}");
+        let _ = stdout().flush();
+
+        let measurement = run_timed(func, input, true, |_| {});
+        print!("\r\x1b[2K");
+
+        results.push(VariantResult {
+            name: (*name).into(),
+            answer: measurement.result.as_ref().map(ToString::to_string),
+            statistics: measurement.statistics.unwrap(),
+        });
+    }
+
+    let (table, is_consistent) = format_variants(&results);
+    println!("{table}\n");
+    is_consistent
+}
+
+/// Exit after comparing variants. Fails if no variants are registered or if any variants disagree.
+pub fn finish_variants(agreements: &[bool]) {
+    if agreements.is_empty() {
+        eprintln!(
+            "No variants registered. Add them to the `solution!` macro, e.g. `solution!(2025, 1, variants: {{ part_one: [part_one_fold] }});`."
+        );
+        process::exit(1);
+    }
+
+    if agreements.iter().any(|x| !x) {
+        eprintln!("Variants returned different answers.");
+        process::exit(1);
+    }
+}
+
+/// Format benched variants as a table, relative to the first variant.
+/// Returns the table and whether all variants returned the same answer as the first one.
+fn format_variants(results: &[VariantResult]) -> (String, bool) {
+    let Some(baseline) = results.first() else {
+        return (String::new(), true);
+    };
+
+    let nanos = |x: f64| {
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        Duration::from_nanos(x.round() as u64)
+    };
+
+    let mut is_consistent = true;
+    let mut rows = vec![[
+        "Variant".to_string(),
+        "Answer".into(),
+        "Mean".into(),
+        "Median".into(),
+        "Relative".into(),
+    ]];
+
+    for (i, result) in results.iter().enumerate() {
+        let mut answer = match &result.answer {
+            Some(answer) if answer.contains('\n') => "▼".to_string(),
+            Some(answer) => answer.clone(),
+            None => "✖".into(),
+        };
+
+        if result.answer != baseline.answer {
+            is_consistent = false;
+            answer.push_str(" ✗");
+        }
+
+        let speedup = baseline.statistics.mean / result.statistics.mean;
+        let relative = if i == 0 {
+            "baseline".to_string()
+        } else if speedup >= 1.0 {
+            format!("{speedup:.2}× faster")
+        } else {
+            format!("{:.2}× slower", 1.0 / speedup)
+        };
+
+        rows.push([
+            result.name.clone(),
+            answer,
+            format!("{:.1?}", nanos(result.statistics.mean)),
+            format!("{:.1?}", nanos(result.statistics.median)),
+            relative,
+        ]);
+    }
+
+    let widths: Vec<usize> = (0..5)
+        .map(|col| rows.iter().map(|r| r[col].chars().count()).max().unwrap())
+        .collect();
+
+    let lines: Vec<String> = rows
+        .iter()
+        .enumerate()
+        .map(|(i, row)| {
+            let line = row
+                .iter()
+                .zip(&widths)
+                .map(|(cell, width)| format!("{cell:<width$}"))
+                .collect::<Vec<_>>()
+                .join("  ");
+            let line = line.trim_end().to_string();
+
+            if i == 0 {
+                format!("{ANSI_BOLD}{line}{ANSI_RESET}")
+            } else {
+                line
+            }
+        })
+        .collect();
+
+    (lines.join("\n"), is_consistent)
+}
+
+/// Inputs of increasing size to measure how a solution scales, see [`run_scaling`].
+/// If the day has a [`Generator`], it generates inputs from an eighth up to four times the size of the real input,
+/// or from a 32nd up to the size passed with `--size`. Otherwise, the real input is truncated by lines.
+pub fn scaling_inputs(puzzle: Puzzle, generator: Option<Generator>) -> Vec<(usize, String)> {
+    let input = try_read_input(puzzle);
+    let size = positive_arg("--size", "cargo solve 1 --scaling --size 1000");
+
+    match (generator, input) {
+        (Some(generator), input) => {
+            let largest = size.unwrap_or_else(|| {
+                // NOTE: without a real input, scale around an arbitrary size.
+                let lines = input.map_or(1000, |x| x.lines().count());
+                cmp::max(lines / 8, 1) << 5
+            });
+
+            scaling::truncated_sizes(largest, 6)
+                .into_iter()
+                .map(|size| (size, generator(size, 1)))
+                .collect()
+        }
+        (None, Ok(input)) => {
+            let lines = input.lines().count();
+
+            scaling::truncated_sizes(size.map_or(lines, |x| x.min(lines)), 6)
+                .into_iter()
+                .map(|size| (size, scaling::truncate_lines(&input, size)))
+                .collect()
+        }
+        (None, Err(e)) => {
+            eprintln!("{e}");
+            process::exit(1);
+        }
+    }
+}
+
+/// Parse a positive `<name> <n>` argument, exiting with an example of the expected format if it is invalid.
+fn positive_arg(name: &str, example: &str) -> Option<usize> {
+    let args: Vec<String> = env::args().collect();
+    let index = args.iter().position(|x| x == name)?;
+
+    match args.get(index + 1).and_then(|x| x.parse().ok()) {
+        Some(value) if value > 0 => Some(value),
+        _ => {
+            eprintln!("Unexpected command-line input. Format: {example}");
+            process::exit(1);
+        }
+    }
+}
+
+/// Bench a part against inputs of increasing size and print the complexity class that fits the timings best.
+pub fn run_scaling<T: Display>(
+    func: impl Fn(&str) -> Option<T>,
+    part: u8,
+    inputs: &[(usize, String)],
+) {
+    println!("{ANSI_BOLD}Part {part}{ANSI_RESET}");
+    println!("{:>10}  {:>10}  {:>10}", "Size", "Mean", "Median");
+
+    let mut samples = vec![];
+
+    for (size, input) in inputs {
+        print!("{size:>10}  > {ANSI_ITALIC}benching{ANSI_RESET}");
+        let _ = stdout().flush();
+
+        let measurement = run_timed(&func, input.as_str(), true, |_| {});
+        let statistics = measurement.statistics.unwrap();
+        let solved = if measurement.result.is_some() {
+            ""
+        } else {
+            "  ✖"
+        };
+
+        println!(
+            "\r\x1b[2K{size:>10}  {:>10}  {:>10}{solved}",
+            format!("{:.1?}", statistics.mean_duration()),
+            format!("{:.1?}", Statistics::to_duration(statistics.median)),
+        );
+        samples.push((*size, statistics.mean));
+    }
+
+    match fit(&samples) {
+        Some(fit) => println!(
+            "≈ {ANSI_BOLD}{}{ANSI_RESET} (t ∝ n^{:.2}, error {:.1}%)\n",
+            fit.complexity,
+            fit.exponent,
+            fit.error * 100.0
+        ),
+        None => println!("Not enough distinct input sizes to estimate the complexity.\n"),
+    }
+}
+
+/// Compare a part with its reference implementation on generated inputs and print the shrunk counterexample
+/// if they disagree, see [`differential::find_counterexample`]. Returns whether they agree.
+///
+/// The number of inputs and their largest size are read from `--runs <n>` (default 100) and `--size <n>` (default 10).
+pub fn check_reference<T: PartialEq + Display>(
+    (name, func): (&str, Part<T>),
+    (reference_name, reference): (&str, Part<T>),
+    generator: Option<Generator>,
+) -> bool {
+    let Some(generator) = generator else {
+        eprintln!(
+            "Checking references needs generated inputs. Register a generator in the `solution!` macro, e.g. `generator: generate`."
+        );
+        process::exit(1);
+    };
+
+    let runs = positive_arg("--runs", "cargo solve 1 --differential --runs 100").unwrap_or(100);
+    let max_size = positive_arg("--size", "cargo solve 1 --differential --size 10").unwrap_or(10);
+
+    print!("{name} = {reference_name} > {ANSI_ITALIC}checking{ANSI_RESET}");
+    let _ = stdout().flush();
+
+    let counterexample =
+        differential::find_counterexample(func, reference, generator, runs as u64, max_size);
+    print!("\r\x1b[2K");
+
+    match counterexample {
+        None => {
+            println!("{name} agrees with {reference_name} on {runs} generated inputs ✓");
+            true
+        }
+        Some(counterexample) => {
+            println!(
+                "{ANSI_BOLD}{name} disagrees with {reference_name}{ANSI_RESET} ✗\n{counterexample}\n"
+            );
+            false
+        }
+    }
+}
+
+/// Exit after checking references. Fails if no references are registered or if any part disagrees with its reference.
+pub fn finish_references(agreements: &[bool]) {
+    if agreements.is_empty() {
+        eprintln!(
+            "No references registered. Add them to the `solution!` macro, e.g. `solution!(2025, 1, references: {{ part_two: part_two_brute_force }});`."
+        );
+        process::exit(1);
+    }
+
+    if agreements.iter().any(|x| !x) {
+        eprintln!("Parts disagreed with their references.");
+        process::exit(1);
+    }
+}
+
+/// Print how a solution solves an input, as SVG if `--svg` is passed and as text otherwise.
+pub fn visualize(input: &str, visualizer: Option<Visualizer>) {
+    let Some(visualizer) = visualizer else {
+        eprintln!(
+            "No visualizer registered. Add one to the `solution!` macro, e.g. `solution!(2025, 1, visualizer: visualize);`."
+        );
+        process::exit(1);
+    };
+
+    let format = if env::args().any(|x| x == "--svg") {
+        Visualization::Svg
+    } else {
+        Visualization::Text
+    };
+
+    match visualizer(input, format) {
+        // NOTE: ignore broken pipes, e.g. when the output is piped into `head`.
+        Ok(output) => {
+            let _ = stdout().write_all(output.as_bytes());
+        }
+        Err(e) => {
+            eprintln!("Could not visualize the input: {e}.");
+            process::exit(1);
+        }
+    }
+}
+
 /// Parse the arguments passed to `solve` and try to submit one part of the solution if:
 ///  1. we are in `--release` mode.
-///  2. aoc-cli is installed.
+///  2. a session cookie is configured, see [`aoc_client::AocClient::from_env`].
+///
+/// Every submission is recorded in the history of its day in `data/<year>/submissions`,
+/// answers that were already rejected are not submitted again.
+/// A correct answer is recorded as the known answer in `data/<year>/answers.json`.
+#[cfg(feature = "client")]
 fn submit_result<T: Display>(
     result: T,
-    day: Day,
+    puzzle: Puzzle,
     part: u8,
-) -> Option<Result<Output, aoc_cli::AocCommandError>> {
+) -> Option<Result<SubmitOutcome, aoc_client::AocClientError>> {
     let args: Vec<String> = env::args().collect();
 
     if !args.contains(&"--submit".into()) {
@@ -155,13 +673,273 @@ fn submit_result<T: Display>(
         return None;
     }
 
-    if aoc_cli::check().is_err() {
+    let answer = result.to_string();
+    let mut submissions = match Submissions::read_from_file(puzzle) {
+        Ok(submissions) => submissions,
+        Err(e) => {
+            eprintln!(
+                "Not submitting {answer} for part {part}: could not read submissions file {e}"
+            );
+            return None;
+        }
+    };
+
+    if let Err(refusal) = submissions.check(part, &answer) {
+        eprintln!("Not submitting {answer} for part {part}: {refusal}.");
+        return None;
+    }
+
+    println!("Submitting result...");
+    let outcome = aoc_client::submit(puzzle, part, &answer);
+
+    if let Err(e) = &outcome {
+        eprintln!("Failed to submit result: {e}");
+    }
+
+    if let Ok(outcome) = &outcome {
+        println!("Submission outcome: {outcome}.");
+
+        submissions.push(part, &answer, outcome.clone());
+        if let Err(e) = submissions.store_file(puzzle) {
+            eprintln!("Failed to store submission: {e}");
+        }
+
+        if *outcome == SubmitOutcome::Correct {
+            match Answers::read_from_file(puzzle.year) {
+                Ok(mut answers) => {
+                    answers.set(puzzle.day, part, &answer);
+
+                    if let Err(e) = answers.store_file(puzzle.year) {
+                        eprintln!("Failed to store answer: {e}");
+                    }
+                }
+                Err(e) => {
+                    eprintln!("Not storing answer {answer}: could not read known answers from {e}");
+                }
+            }
+        }
+    }
+
+    Some(outcome)
+}
+
+/// Solutions are built without the HTTP client unless `cargo solve <day> --submit <part>` enables it.
+#[cfg(not(feature = "client"))]
+fn submit_result<T: Display>(_result: T, _puzzle: Puzzle, _part: u8) {
+    if env::args().any(|x| x == "--submit") {
         eprintln!(
-            "command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it."
+            "Submitting requires the `client` feature, run `cargo solve <day> --submit <part>`."
         );
         process::exit(1);
     }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&PartResult> for JsonValue {
+    fn from(value: &PartResult) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert("part".into(), JsonValue::Number(f64::from(value.part)));
+        map.insert(
+            "answer".into(),
+            value
+                .answer
+                .clone()
+                .map_or(JsonValue::Null, JsonValue::String),
+        );
+        map.insert(
+            "duration_nanos".into(),
+            JsonValue::Number(value.duration_nanos),
+        );
+        #[allow(clippy::cast_precision_loss)]
+        map.insert("samples".into(), JsonValue::Number(value.samples as f64));
+        map.insert(
+            "statistics".into(),
+            value
+                .statistics
+                .as_ref()
+                .map_or(JsonValue::Null, JsonValue::from),
+        );
+        map.insert(
+            "heap".into(),
+            value.heap.as_ref().map_or(JsonValue::Null, JsonValue::from),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&str> for PartResult {
+    type Error = String;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(value).or(Err("not valid JSON."))?;
+
+        let json = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected part result to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected result.day to be a Day struct.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let part = json
+            .get("part")
+            .and_then(|v| v.get::<f64>())
+            .map(|x| *x as u8)
+            .ok_or("Expected result.part to be a number.")?;
 
-    println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+        let answer = json
+            .get("answer")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected result.answer to be null or string.")?;
+
+        let duration_nanos = json
+            .get("duration_nanos")
+            .and_then(|v| v.get::<f64>().copied())
+            .ok_or("Expected result.duration_nanos to be a number.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let samples = json
+            .get("samples")
+            .and_then(|v| v.get::<f64>())
+            .map(|x| *x as u128)
+            .ok_or("Expected result.samples to be a number.")?;
+
+        let statistics = match json.get("statistics") {
+            None | Some(JsonValue::Null) => None,
+            Some(v) => Some(Statistics::try_from(v)?),
+        };
+
+        let heap = match json.get("heap") {
+            None | Some(JsonValue::Null) => None,
+            Some(v) => Some(HeapStatistics::try_from(v)?),
+        };
+
+        Ok(PartResult {
+            day,
+            part,
+            answer: answer.cloned(),
+            duration_nanos,
+            samples,
+            statistics,
+            heap,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use tinyjson::JsonValue;
+
+    use super::{PartResult, VariantResult, format_variants};
+    use crate::{
+        day,
+        template::timings::{HeapStatistics, Statistics},
+    };
+
+    #[test]
+    fn roundtrips_part_results() {
+        let result = PartResult {
+            day: day!(8),
+            part: 2,
+            answer: Some("@ ( 12 samples)\nline".into()),
+            duration_nanos: 74.0,
+            samples: 100_000,
+            statistics: Some(Statistics {
+                mean: 74.0,
+                min: 70.0,
+                median: 73.0,
+                p95: 80.0,
+                max: 120.0,
+                std_dev: 3.5,
+            }),
+            heap: Some(HeapStatistics {
+                peak_bytes: 232,
+                total_allocations: 3,
+                total_bytes: 276,
+            }),
+        };
+
+        let json = JsonValue::from(&result).stringify().unwrap();
+        assert_eq!(json.lines().count(), 1);
+        assert_eq!(PartResult::try_from(json.as_str()).unwrap(), result);
+    }
+
+    #[test]
+    fn parses_unsolved_parts() {
+        let result = PartResult::try_from(
+            r#"{ "day": "01", "part": 1, "answer": null, "duration_nanos": 10, "samples": 1, "statistics": null }"#,
+        )
+        .unwrap();
+        assert_eq!(result.day, day!(1));
+        assert_eq!(result.answer, None);
+        assert_eq!(result.statistics, None);
+        assert_eq!(result.heap, None);
+    }
+
+    #[test]
+    fn rejects_text_output() {
+        assert!(PartResult::try_from("Part 1: 0 (74.13ns @ 100000 samples)").is_err());
+    }
+
+    fn get_mock_variant(name: &str, answer: Option<&str>, mean: f64) -> VariantResult {
+        VariantResult {
+            name: name.into(),
+            answer: answer.map(Into::into),
+            statistics: Statistics {
+                mean,
+                min: mean,
+                median: mean,
+                p95: mean,
+                max: mean,
+                std_dev: 0.0,
+            },
+        }
+    }
+
+    #[test]
+    fn formats_variants() {
+        let (table, is_consistent) = format_variants(&[
+            get_mock_variant("part_one", Some("42"), 200.0),
+            get_mock_variant("part_one_fast", Some("42"), 100.0),
+            get_mock_variant("part_one_slow", Some("42"), 800.0),
+        ]);
+
+        assert!(is_consistent);
+        let lines: Vec<_> = table.lines().collect();
+        assert_eq!(lines.len(), 4);
+        assert_eq!(
+            lines[1],
+            "part_one       42      200.0ns  200.0ns  baseline"
+        );
+        assert_eq!(
+            lines[2],
+            "part_one_fast  42      100.0ns  100.0ns  2.00× faster"
+        );
+        assert_eq!(
+            lines[3],
+            "part_one_slow  42      800.0ns  800.0ns  4.00× slower"
+        );
+    }
+
+    #[test]
+    fn flags_disagreeing_variants() {
+        let (table, is_consistent) = format_variants(&[
+            get_mock_variant("part_two", Some("42"), 100.0),
+            get_mock_variant("part_two_wrong", Some("41"), 100.0),
+            get_mock_variant("part_two_unsolved", None, 100.0),
+        ]);
+
+        assert!(!is_consistent);
+        assert!(table.contains("part_two_wrong     41 ✗"));
+        assert!(table.contains("part_two_unsolved  ✖ ✗"));
+    }
 }
@@ -0,0 +1,192 @@
+/// Module that estimates how the runtime of a solution grows with the size of its input.
+use std::fmt::Display;
+
+/// A complexity class that measured timings can be fitted to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Complexity {
+    Constant,
+    Logarithmic,
+    Linear,
+    Linearithmic,
+    Quadratic,
+    Cubic,
+}
+
+impl Complexity {
+    const ALL: [Complexity; 6] = [
+        Complexity::Constant,
+        Complexity::Logarithmic,
+        Complexity::Linear,
+        Complexity::Linearithmic,
+        Complexity::Quadratic,
+        Complexity::Cubic,
+    ];
+
+    /// The growth function of the class.
+    fn eval(self, n: f64) -> f64 {
+        match self {
+            Complexity::Constant => 1.0,
+            Complexity::Logarithmic => n.max(2.0).ln(),
+            Complexity::Linear => n,
+            Complexity::Linearithmic => n * n.max(2.0).ln(),
+            Complexity::Quadratic => n * n,
+            Complexity::Cubic => n * n * n,
+        }
+    }
+}
+
+impl Display for Complexity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Complexity::Constant => "O(1)",
+            Complexity::Logarithmic => "O(log n)",
+            Complexity::Linear => "O(n)",
+            Complexity::Linearithmic => "O(n log n)",
+            Complexity::Quadratic => "O(n²)",
+            Complexity::Cubic => "O(n³)",
+        })
+    }
+}
+
+/// The complexity class that describes a set of measurements best.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Fit {
+    pub complexity: Complexity,
+    /// Root mean square of the relative errors of the fitted class.
+    pub error: f64,
+    /// Slope of the measurements on a log-log scale, i.e. `k` in `t ≈ c·nᵏ`.
+    pub exponent: f64,
+}
+
+/// Fit measurements of `(size, nanos)` to the complexity class with the smallest relative error.
+/// Returns `None` if there are less than three measurements of distinct sizes.
+pub fn fit(samples: &[(usize, f64)]) -> Option<Fit> {
+    #[allow(clippy::cast_precision_loss)]
+    let samples: Vec<(f64, f64)> = samples
+        .iter()
+        .filter(|(n, t)| *n > 0 && *t > 0.0)
+        .map(|(n, t)| (*n as f64, *t))
+        .collect();
+
+    let mut sizes: Vec<f64> = samples.iter().map(|(n, _)| *n).collect();
+    sizes.sort_unstable_by(f64::total_cmp);
+    sizes.dedup();
+
+    if sizes.len() < 3 {
+        return None;
+    }
+
+    let (complexity, error) = Complexity::ALL
+        .iter()
+        .map(|&complexity| (complexity, relative_error(&samples, complexity)))
+        .min_by(|a, b| a.1.total_cmp(&b.1))?;
+
+    Some(Fit {
+        complexity,
+        error,
+        exponent: log_log_slope(&samples),
+    })
+}
+
+/// Fit `t = c·f(n)` by minimizing relative errors and return the root mean square of the relative errors.
+fn relative_error(samples: &[(f64, f64)], complexity: Complexity) -> f64 {
+    // NOTE: minimizing Σ((c·f - t) / t)² gives c = Σ(f/t) / Σ(f/t)².
+    let ratios: Vec<f64> = samples
+        .iter()
+        .map(|(n, t)| complexity.eval(*n) / t)
+        .collect();
+    let c = ratios.iter().sum::<f64>() / ratios.iter().map(|r| r * r).sum::<f64>();
+
+    #[allow(clippy::cast_precision_loss)]
+    let len = ratios.len() as f64;
+    (ratios.iter().map(|r| (c * r - 1.0).powi(2)).sum::<f64>() / len).sqrt()
+}
+
+/// Least squares slope of `ln t` over `ln n`.
+fn log_log_slope(samples: &[(f64, f64)]) -> f64 {
+    let points: Vec<(f64, f64)> = samples.iter().map(|(n, t)| (n.ln(), t.ln())).collect();
+
+    #[allow(clippy::cast_precision_loss)]
+    let len = points.len() as f64;
+    let mean_x = points.iter().map(|(x, _)| x).sum::<f64>() / len;
+    let mean_y = points.iter().map(|(_, y)| y).sum::<f64>() / len;
+
+    let covariance: f64 = points
+        .iter()
+        .map(|(x, y)| (x - mean_x) * (y - mean_y))
+        .sum();
+    let variance: f64 = points.iter().map(|(x, _)| (x - mean_x).powi(2)).sum();
+
+    covariance / variance
+}
+
+/// Sizes to scale a real input with `lines` lines down to, halving it up to `steps - 1` times.
+pub fn truncated_sizes(lines: usize, steps: u32) -> Vec<usize> {
+    let mut sizes: Vec<usize> = (0..steps)
+        .rev()
+        .map(|i| lines >> i)
+        .filter(|x| *x > 0)
+        .collect();
+    sizes.dedup();
+    sizes
+}
+
+/// The first `count` lines of an input, keeping the trailing newline.
+pub fn truncate_lines(input: &str, count: usize) -> String {
+    input.split_inclusive('\n').take(count).collect::<String>()
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Complexity, fit, truncate_lines, truncated_sizes};
+
+    fn measure(f: impl Fn(f64) -> f64) -> Vec<(usize, f64)> {
+        [100_u32, 200, 400, 800, 1600, 3200]
+            .iter()
+            .map(|&n| (n as usize, f(f64::from(n))))
+            .collect()
+    }
+
+    #[test]
+    fn fits_complexity_classes() {
+        let cases: [(Complexity, fn(f64) -> f64); 5] = [
+            (Complexity::Constant, |_| 500.0),
+            (Complexity::Linear, |n| 3.0 * n + 20.0),
+            (Complexity::Linearithmic, |n| 2.0 * n * n.ln()),
+            (Complexity::Quadratic, |n| 0.5 * n * n + 100.0),
+            (Complexity::Cubic, |n| 0.01 * n * n * n),
+        ];
+
+        for (expected, f) in cases {
+            let fit = fit(&measure(f)).unwrap();
+            assert_eq!(fit.complexity, expected);
+            assert!(fit.error < 0.1);
+        }
+    }
+
+    #[test]
+    fn estimates_exponents() {
+        let fit = fit(&measure(|n| 4.0 * n * n)).unwrap();
+        assert!((fit.exponent - 2.0).abs() < 1e-9);
+
+        let fit = super::fit(&measure(|n| 7.0 * n)).unwrap();
+        assert!((fit.exponent - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn needs_three_sizes() {
+        assert_eq!(fit(&[(10, 1.0), (20, 2.0), (20, 2.0)]), None);
+        assert_eq!(fit(&[(0, 1.0), (10, 1.0), (20, 2.0)]), None);
+        assert!(fit(&[(10, 1.0), (20, 2.0), (40, 4.0)]).is_some());
+    }
+
+    #[test]
+    fn scales_real_inputs() {
+        assert_eq!(truncated_sizes(1000, 4), vec![125, 250, 500, 1000]);
+        assert_eq!(truncated_sizes(3, 4), vec![1, 3]);
+        assert_eq!(truncate_lines("a\nb\nc\n", 2), "a\nb\n");
+        assert_eq!(truncate_lines("a\nb", 5), "a\nb");
+    }
+}
@@ -0,0 +1,510 @@
+/// Module that keeps a per-day history of submitted answers and their outcomes.
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    fs,
+    io::{Error, ErrorKind},
+    path::PathBuf,
+    str::FromStr,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use tinyjson::JsonValue;
+
+use crate::template::Puzzle;
+
+/// The response of the Advent of Code server to a submitted answer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SubmitOutcome {
+    /// The answer was accepted.
+    Correct,
+    /// The answer was rejected for being too high.
+    TooHigh,
+    /// The answer was rejected for being too low.
+    TooLow,
+    /// The answer was rejected without a hint.
+    Incorrect,
+    /// An answer was submitted too recently. Contains the remaining wait time, if it could be parsed.
+    RateLimited(Option<Duration>),
+    /// The part was already solved, or is not unlocked yet.
+    WrongLevel,
+    /// The response could not be interpreted.
+    Unknown,
+}
+
+impl SubmitOutcome {
+    /// Interpret the text of a response to a submission.
+    pub fn from_response(response: &str) -> Self {
+        if response.contains("That's the right answer") {
+            SubmitOutcome::Correct
+        } else if response.contains("That's not the right answer") {
+            if response.contains("too high") {
+                SubmitOutcome::TooHigh
+            } else if response.contains("too low") {
+                SubmitOutcome::TooLow
+            } else {
+                SubmitOutcome::Incorrect
+            }
+        } else if response.contains("You gave an answer too recently") {
+            SubmitOutcome::RateLimited(parse_wait_time(response))
+        } else if response.contains("You don't seem to be solving the right level") {
+            SubmitOutcome::WrongLevel
+        } else {
+            SubmitOutcome::Unknown
+        }
+    }
+
+    /// Whether the server rejected the answer as wrong.
+    pub fn is_rejection(&self) -> bool {
+        matches!(
+            self,
+            SubmitOutcome::TooHigh | SubmitOutcome::TooLow | SubmitOutcome::Incorrect
+        )
+    }
+
+    fn key(&self) -> &'static str {
+        match self {
+            SubmitOutcome::Correct => "correct",
+            SubmitOutcome::TooHigh => "too_high",
+            SubmitOutcome::TooLow => "too_low",
+            SubmitOutcome::Incorrect => "incorrect",
+            SubmitOutcome::RateLimited(_) => "rate_limited",
+            SubmitOutcome::WrongLevel => "wrong_level",
+            SubmitOutcome::Unknown => "unknown",
+        }
+    }
+}
+
+impl Display for SubmitOutcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SubmitOutcome::Correct => write!(f, "correct"),
+            SubmitOutcome::TooHigh => write!(f, "incorrect, too high"),
+            SubmitOutcome::TooLow => write!(f, "incorrect, too low"),
+            SubmitOutcome::Incorrect => write!(f, "incorrect"),
+            SubmitOutcome::RateLimited(Some(wait)) => {
+                write!(f, "rate-limited, {}s left to wait", wait.as_secs())
+            }
+            SubmitOutcome::RateLimited(None) => write!(f, "rate-limited"),
+            SubmitOutcome::WrongLevel => write!(f, "already solved or not unlocked yet"),
+            SubmitOutcome::Unknown => write!(f, "unknown response"),
+        }
+    }
+}
+
+/// Parse the wait time of a rate-limit response, e.g. `You have 1m 2s left to wait.`
+fn parse_wait_time(response: &str) -> Option<Duration> {
+    let (_, rest) = response.split_once("You have ")?;
+    let (time, _) = rest.split_once(" left to wait")?;
+
+    time.split_whitespace()
+        .map(|token| {
+            let (value, unit) = token.split_at(token.find(|c: char| !c.is_ascii_digit())?);
+            let value: u64 = value.parse().ok()?;
+
+            match unit {
+                "h" => Some(value * 3600),
+                "m" => Some(value * 60),
+                "s" => Some(value),
+                _ => None,
+            }
+        })
+        .sum::<Option<u64>>()
+        .map(Duration::from_secs)
+}
+
+/// The reason an answer is not submitted, derived from earlier submissions.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Refusal {
+    /// The same answer was rejected before.
+    AlreadyRejected(Submission),
+    /// The answer is not below an answer that was rejected as too high.
+    NotBelow(Submission),
+    /// The answer is not above an answer that was rejected as too low.
+    NotAbove(Submission),
+}
+
+impl Display for Refusal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Refusal::AlreadyRejected(s) => write!(f, "it was already rejected ({})", s.outcome),
+            Refusal::NotBelow(s) => write!(f, "{} was already rejected as too high", s.answer),
+            Refusal::NotAbove(s) => write!(f, "{} was already rejected as too low", s.answer),
+        }
+    }
+}
+
+/// A single submitted answer.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Submission {
+    pub part: u8,
+    pub answer: String,
+    pub outcome: SubmitOutcome,
+    /// Unix timestamp of the submission, in seconds.
+    pub timestamp: f64,
+}
+
+/// All submissions for a day, in order of submission.
+/// Can be serialized from / to JSON.
+#[derive(Clone, Debug, Default)]
+pub struct Submissions {
+    pub data: Vec<Submission>,
+}
+
+impl Submissions {
+    /// Dehydrate the submissions of a puzzle to a JSON file.
+    pub fn store_file(&self, puzzle: Puzzle) -> Result<(), Error> {
+        let path = PathBuf::from(puzzle.data_path("submissions", "json"));
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let json = JsonValue::from(self.clone());
+        let mut file = fs::File::create(path)?;
+        json.format_to(&mut file)
+    }
+
+    /// Rehydrate the submissions of a puzzle from a JSON file. If not present, returns an empty history.
+    /// Fails if the file can not be read or parsed, so that a corrupt history is never overwritten.
+    pub fn read_from_file(puzzle: Puzzle) -> Result<Self, String> {
+        let path = puzzle.data_path("submissions", "json");
+
+        match fs::read_to_string(&path) {
+            Ok(contents) => Submissions::try_from(contents).map_err(|e| format!("\"{path}\": {e}")),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(Submissions::default()),
+            Err(e) => Err(format!("\"{path}\": {e}")),
+        }
+    }
+
+    /// Record a submission that happened just now.
+    pub fn push(&mut self, part: u8, answer: &str, outcome: SubmitOutcome) {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|x| x.as_secs_f64())
+            .unwrap_or_default();
+
+        self.data.push(Submission {
+            part,
+            answer: answer.into(),
+            outcome,
+            timestamp,
+        });
+    }
+
+    /// Find an earlier submission of the same answer that was rejected.
+    pub fn find_rejection(&self, part: u8, answer: &str) -> Option<&Submission> {
+        self.data
+            .iter()
+            .find(|s| s.part == part && s.answer == answer && s.outcome.is_rejection())
+    }
+
+    /// Check an answer against earlier submissions before submitting it.
+    /// Numeric answers must lie strictly between the highest answer rejected as too low
+    /// and the lowest answer rejected as too high.
+    pub fn check(&self, part: u8, answer: &str) -> Result<(), Refusal> {
+        if let Some(rejection) = self.find_rejection(part, answer) {
+            return Err(Refusal::AlreadyRejected(rejection.clone()));
+        }
+
+        let Ok(value) = answer.trim().parse::<i128>() else {
+            return Ok(());
+        };
+
+        let bounds = self.data.iter().filter(|s| s.part == part).filter_map(|s| {
+            let bound = s.answer.trim().parse::<i128>().ok()?;
+            Some((bound, s))
+        });
+
+        let mut upper: Option<(i128, &Submission)> = None;
+        let mut lower: Option<(i128, &Submission)> = None;
+
+        for (bound, submission) in bounds {
+            match submission.outcome {
+                SubmitOutcome::TooHigh if upper.is_none_or(|(x, _)| bound < x) => {
+                    upper = Some((bound, submission));
+                }
+                SubmitOutcome::TooLow if lower.is_none_or(|(x, _)| bound > x) => {
+                    lower = Some((bound, submission));
+                }
+                _ => {}
+            }
+        }
+
+        match (upper, lower) {
+            (Some((bound, submission)), _) if value >= bound => {
+                Err(Refusal::NotBelow(submission.clone()))
+            }
+            (_, Some((bound, submission))) if value <= bound => {
+                Err(Refusal::NotAbove(submission.clone()))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<Submissions> for JsonValue {
+    fn from(value: Submissions) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for Submissions {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json_data = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("data")
+            .ok_or("expected JSON document to have key `data`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.data` to be an array.")?;
+
+        Ok(Submissions {
+            data: json_data
+                .iter()
+                .map(Submission::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Submission> for JsonValue {
+    fn from(value: &Submission) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("part".into(), JsonValue::Number(f64::from(value.part)));
+        map.insert("answer".into(), JsonValue::String(value.answer.clone()));
+        map.insert(
+            "outcome".into(),
+            JsonValue::String(value.outcome.key().into()),
+        );
+        map.insert("timestamp".into(), JsonValue::Number(value.timestamp));
+
+        #[allow(clippy::cast_precision_loss)]
+        let wait = match value.outcome {
+            SubmitOutcome::RateLimited(Some(wait)) => JsonValue::Number(wait.as_secs() as f64),
+            _ => JsonValue::Null,
+        };
+        map.insert("wait_seconds".into(), wait);
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Submission {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected submission to be a JSON object.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let part = json
+            .get("part")
+            .and_then(|v| v.get::<f64>())
+            .map(|x| *x as u8)
+            .ok_or("Expected submission.part to be a number.")?;
+
+        let answer = json
+            .get("answer")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected submission.answer to be a string.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let wait = json
+            .get("wait_seconds")
+            .and_then(|v| v.get::<f64>())
+            .map(|x| Duration::from_secs(*x as u64));
+
+        let outcome = match json.get("outcome").and_then(|v| v.get::<String>()) {
+            Some(x) if x == "correct" => SubmitOutcome::Correct,
+            Some(x) if x == "too_high" => SubmitOutcome::TooHigh,
+            Some(x) if x == "too_low" => SubmitOutcome::TooLow,
+            Some(x) if x == "incorrect" => SubmitOutcome::Incorrect,
+            Some(x) if x == "rate_limited" => SubmitOutcome::RateLimited(wait),
+            Some(x) if x == "wrong_level" => SubmitOutcome::WrongLevel,
+            Some(x) if x == "unknown" => SubmitOutcome::Unknown,
+            _ => return Err("Expected submission.outcome to be a known outcome.".into()),
+        };
+
+        let timestamp = json
+            .get("timestamp")
+            .and_then(|v| v.get::<f64>().copied())
+            .ok_or("Expected submission.timestamp to be a number.")?;
+
+        Ok(Submission {
+            part,
+            answer: answer.clone(),
+            outcome,
+            timestamp,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{Refusal, Submissions, SubmitOutcome};
+
+    mod outcome {
+        use super::*;
+
+        #[test]
+        fn parses_correct_answers() {
+            let response = "That's the right answer! You are one gold star closer to decorating the North Pole.";
+            assert_eq!(
+                SubmitOutcome::from_response(response),
+                SubmitOutcome::Correct
+            );
+        }
+
+        #[test]
+        fn parses_incorrect_answers() {
+            let too_high = "That's not the right answer; your answer is too high. If you're stuck, make sure you're using the full input data. Please wait one minute before trying again.";
+            let too_low = "That's not the right answer; your answer is too low. Please wait one minute before trying again.";
+            let incorrect = "That's not the right answer. If you're stuck, make sure you're using the full input data.";
+
+            assert_eq!(
+                SubmitOutcome::from_response(too_high),
+                SubmitOutcome::TooHigh
+            );
+            assert_eq!(SubmitOutcome::from_response(too_low), SubmitOutcome::TooLow);
+            assert_eq!(
+                SubmitOutcome::from_response(incorrect),
+                SubmitOutcome::Incorrect
+            );
+        }
+
+        #[test]
+        fn parses_rate_limits() {
+            let response = "You gave an answer too recently; you have to wait after submitting an answer before trying again. You have 1m 2s left to wait.";
+            assert_eq!(
+                SubmitOutcome::from_response(response),
+                SubmitOutcome::RateLimited(Some(Duration::from_secs(62)))
+            );
+
+            let response = "You gave an answer too recently; you have to wait after submitting an answer before trying again. You have 34s left to wait.";
+            assert_eq!(
+                SubmitOutcome::from_response(response),
+                SubmitOutcome::RateLimited(Some(Duration::from_secs(34)))
+            );
+
+            let response = "You gave an answer too recently.";
+            assert_eq!(
+                SubmitOutcome::from_response(response),
+                SubmitOutcome::RateLimited(None)
+            );
+        }
+
+        #[test]
+        fn parses_other_responses() {
+            let response =
+                "You don't seem to be solving the right level.  Did you already complete it?";
+            assert_eq!(
+                SubmitOutcome::from_response(response),
+                SubmitOutcome::WrongLevel
+            );
+            assert_eq!(
+                SubmitOutcome::from_response("<html>"),
+                SubmitOutcome::Unknown
+            );
+        }
+    }
+
+    mod history {
+        use super::*;
+
+        #[test]
+        fn finds_rejected_answers() {
+            let mut submissions = Submissions::default();
+            submissions.push(1, "42", SubmitOutcome::TooHigh);
+            submissions.push(1, "41", SubmitOutcome::RateLimited(None));
+            submissions.push(2, "40", SubmitOutcome::Correct);
+
+            assert!(submissions.find_rejection(1, "42").is_some());
+            assert!(submissions.find_rejection(2, "42").is_none());
+            assert!(submissions.find_rejection(1, "41").is_none());
+            assert!(submissions.find_rejection(2, "40").is_none());
+        }
+
+        #[test]
+        fn refuses_rejected_answers() {
+            let mut submissions = Submissions::default();
+            submissions.push(1, "abc", SubmitOutcome::Incorrect);
+
+            assert!(matches!(
+                submissions.check(1, "abc"),
+                Err(Refusal::AlreadyRejected(_))
+            ));
+            assert_eq!(submissions.check(1, "abd"), Ok(()));
+            assert_eq!(submissions.check(2, "abc"), Ok(()));
+        }
+
+        #[test]
+        fn refuses_out_of_bounds_answers() {
+            let mut submissions = Submissions::default();
+            submissions.push(1, "100", SubmitOutcome::TooHigh);
+            submissions.push(1, "80", SubmitOutcome::TooHigh);
+            submissions.push(1, "10", SubmitOutcome::TooLow);
+            submissions.push(1, "20", SubmitOutcome::TooLow);
+            submissions.push(1, "50", SubmitOutcome::Incorrect);
+
+            match submissions.check(1, "90") {
+                Err(Refusal::NotBelow(s)) => assert_eq!(s.answer, "80"),
+                x => panic!("unexpected check result: {x:?}"),
+            }
+            match submissions.check(1, "15") {
+                Err(Refusal::NotAbove(s)) => assert_eq!(s.answer, "20"),
+                x => panic!("unexpected check result: {x:?}"),
+            }
+            assert!(matches!(
+                submissions.check(1, "50"),
+                Err(Refusal::AlreadyRejected(_))
+            ));
+            assert!(submissions.check(1, "80").is_err());
+            assert!(submissions.check(1, "20").is_err());
+            assert_eq!(submissions.check(1, "21"), Ok(()));
+            assert_eq!(submissions.check(1, "79"), Ok(()));
+            assert_eq!(submissions.check(1, "not a number"), Ok(()));
+            assert_eq!(submissions.check(2, "1000"), Ok(()));
+        }
+
+        #[test]
+        fn roundtrips_json() {
+            let mut submissions = Submissions::default();
+            submissions.push(1, "42", SubmitOutcome::TooLow);
+            submissions.push(
+                1,
+                "43",
+                SubmitOutcome::RateLimited(Some(Duration::from_secs(30))),
+            );
+
+            let json = tinyjson::JsonValue::from(submissions.clone());
+            let reparsed = Submissions::try_from(json.stringify().unwrap()).unwrap();
+            assert_eq!(reparsed.data, submissions.data);
+        }
+
+        #[test]
+        fn rejects_unknown_outcomes() {
+            let json =
+                r#"{ "data": [{ "part": 1, "answer": "1", "outcome": "maybe", "timestamp": 0 }] }"#;
+            assert!(Submissions::try_from(json.to_string()).is_err());
+        }
+    }
+}
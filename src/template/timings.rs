@@ -1,9 +1,7 @@
-use std::{collections::HashMap, fs, io::Error, str::FromStr};
+use std::{collections::HashMap, fs, io::Error, str::FromStr, time::Duration};
 use tinyjson::JsonValue;
 
-use crate::template::Day;
-
-static TIMINGS_FILE_PATH: &str = "./data/timings.json";
+use crate::template::{Day, Year};
 
 /// Represents benchmark times for a single day.
 #[derive(Clone, Debug)]
@@ -11,9 +9,104 @@ pub struct Timing {
     pub day: Day,
     pub part_1: Option<String>,
     pub part_2: Option<String>,
+    pub part_1_stats: Option<Statistics>,
+    pub part_2_stats: Option<Statistics>,
+    pub part_1_heap: Option<HeapStatistics>,
+    pub part_2_heap: Option<HeapStatistics>,
     pub total_nanos: f64,
 }
 
+/// Summary statistics over the samples of a benched part. All values are in nanoseconds.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Statistics {
+    pub mean: f64,
+    pub min: f64,
+    pub median: f64,
+    pub p95: f64,
+    pub max: f64,
+    pub std_dev: f64,
+}
+
+impl Statistics {
+    /// Compute statistics for a set of samples. Percentiles use the nearest-rank method.
+    ///
+    /// # Panics
+    /// Panics if `samples` is empty.
+    pub fn from_samples(samples: &[Duration]) -> Self {
+        assert!(!samples.is_empty(), "expected at least one sample.");
+
+        #[allow(clippy::cast_precision_loss)]
+        let mut nanos: Vec<f64> = samples.iter().map(|x| x.as_nanos() as f64).collect();
+        nanos.sort_unstable_by(f64::total_cmp);
+
+        #[allow(clippy::cast_precision_loss)]
+        let len = nanos.len() as f64;
+        let mean = nanos.iter().sum::<f64>() / len;
+        let variance = nanos.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / len;
+
+        let mid = nanos.len() / 2;
+        let median = if nanos.len().is_multiple_of(2) {
+            f64::midpoint(nanos[mid - 1], nanos[mid])
+        } else {
+            nanos[mid]
+        };
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let p95_rank = (0.95 * len).ceil() as usize;
+
+        Statistics {
+            mean,
+            min: nanos[0],
+            median,
+            p95: nanos[p95_rank.clamp(1, nanos.len()) - 1],
+            max: nanos[nanos.len() - 1],
+            std_dev: variance.sqrt(),
+        }
+    }
+
+    /// The mean of all samples as a [`Duration`].
+    pub fn mean_duration(&self) -> Duration {
+        Self::to_duration(self.mean)
+    }
+
+    /// Convert one of the statistics to a [`Duration`].
+    pub fn to_duration(nanos: f64) -> Duration {
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        Duration::from_nanos(nanos.round() as u64)
+    }
+}
+
+/// Heap usage of a single run of a part, measured with DHAT.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HeapStatistics {
+    /// Maximum number of bytes allocated at the same time.
+    pub peak_bytes: u64,
+    /// Number of allocations.
+    pub total_allocations: u64,
+    /// Number of bytes allocated over the whole run.
+    pub total_bytes: u64,
+}
+
+/// Format a number of bytes with a binary unit, e.g. `1.5 KiB`.
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+
+    format!("{value:.1} {}", UNITS[unit])
+}
+
 /// Represents benchmark times for a set of days.
 /// Can be serialized from / to JSON.
 #[derive(Clone, Debug, Default)]
@@ -22,27 +115,40 @@ pub struct Timings {
 }
 
 impl Timings {
-    /// Dehydrate timings to a JSON file.
-    pub fn store_file(&self) -> Result<(), Error> {
+    /// Dehydrate the timings of a year to a JSON file.
+    pub fn store_file(&self, year: Year) -> Result<(), Error> {
+        fs::create_dir_all(format!("./data/{year}"))?;
         let json = JsonValue::from(self.clone());
-        let mut file = fs::File::create(TIMINGS_FILE_PATH)?;
+        let mut file = fs::File::create(get_path(year))?;
         json.format_to(&mut file)
     }
 
-    /// Rehydrate timings from a JSON file. If not present, returns empty timings.
-    pub fn read_from_file() -> Self {
-        fs::read_to_string(TIMINGS_FILE_PATH)
+    /// Rehydrate the timings of a year from a JSON file. If not present, returns empty timings.
+    pub fn read_from_file(year: Year) -> Self {
+        fs::read_to_string(get_path(year))
             .map_err(|x| x.to_string())
             .and_then(Timings::try_from)
             .unwrap_or_default()
     }
 
     /// Merge two sets of timings, overwriting `self` with `other` if present.
+    /// Heap statistics are only measured when profiling with DHAT, earlier measurements are kept if `other` has none.
     pub fn merge(&self, new: &Self) -> Self {
         let mut data: Vec<Timing> = vec![];
 
         for timing in &new.data {
-            data.push(timing.clone());
+            let mut timing = timing.clone();
+
+            if let Some(old) = self.data.iter().find(|t| t.day == timing.day) {
+                if timing.part_1_heap.is_none() {
+                    timing.part_1_heap.clone_from(&old.part_1_heap);
+                }
+                if timing.part_2_heap.is_none() {
+                    timing.part_2_heap.clone_from(&old.part_2_heap);
+                }
+            }
+
+            data.push(timing);
         }
 
         for timing in &self.data {
@@ -51,13 +157,33 @@ impl Timings {
             }
         }
 
-        data.sort_unstable_by(|a, b| a.day.cmp(&b.day));
+        data.sort_unstable_by_key(|a| a.day);
         Timings { data }
     }
 
+    /// Merge only the heap statistics of `other` into `self`, keeping the stored durations.
+    /// Used for runs with the DHAT allocator, whose durations are not comparable. Days without stored timings are skipped.
+    pub fn merge_heap(&self, other: &Self) -> Self {
+        let mut merged = self.clone();
+
+        for timing in &mut merged.data {
+            if let Some(new) = other.data.iter().find(|t| t.day == timing.day) {
+                if new.part_1_heap.is_some() {
+                    timing.part_1_heap.clone_from(&new.part_1_heap);
+                }
+                if new.part_2_heap.is_some() {
+                    timing.part_2_heap.clone_from(&new.part_2_heap);
+                }
+            }
+        }
+
+        merged
+    }
+
     /// Sum up total duration of timings as millis.
     pub fn total_millis(&self) -> f64 {
-        self.data.iter().map(|x| x.total_nanos).sum::<f64>() / 1_000_000_f64
+        // NOTE: `sum` of an empty iterator of floats is `-0.0`.
+        self.data.iter().fold(0.0, |acc, x| acc + x.total_nanos) / 1_000_000_f64
     }
 
     pub fn is_day_complete(&self, day: Day) -> bool {
@@ -67,6 +193,10 @@ impl Timings {
     }
 }
 
+fn get_path(year: Year) -> String {
+    format!("./data/{year}/timings.json")
+}
+
 /* -------------------------------------------------------------------------- */
 
 impl From<Timings> for JsonValue {
@@ -133,6 +263,38 @@ impl From<&Timing> for JsonValue {
             },
         );
 
+        map.insert(
+            "part_1_stats".into(),
+            value
+                .part_1_stats
+                .as_ref()
+                .map_or(JsonValue::Null, JsonValue::from),
+        );
+
+        map.insert(
+            "part_2_stats".into(),
+            value
+                .part_2_stats
+                .as_ref()
+                .map_or(JsonValue::Null, JsonValue::from),
+        );
+
+        map.insert(
+            "part_1_heap".into(),
+            value
+                .part_1_heap
+                .as_ref()
+                .map_or(JsonValue::Null, JsonValue::from),
+        );
+
+        map.insert(
+            "part_2_heap".into(),
+            value
+                .part_2_heap
+                .as_ref()
+                .map_or(JsonValue::Null, JsonValue::from),
+        );
+
         JsonValue::Object(map)
     }
 }
@@ -166,10 +328,35 @@ impl TryFrom<&JsonValue> for Timing {
             .and_then(|v| v.get::<f64>().copied())
             .ok_or("Expected timing.total_nanos to be a number.")?;
 
+        // NOTE: statistics were added later, files written by older versions do not contain them.
+        let part_1_stats = match json.get("part_1_stats") {
+            None | Some(JsonValue::Null) => None,
+            Some(v) => Some(Statistics::try_from(v)?),
+        };
+
+        let part_2_stats = match json.get("part_2_stats") {
+            None | Some(JsonValue::Null) => None,
+            Some(v) => Some(Statistics::try_from(v)?),
+        };
+
+        let part_1_heap = match json.get("part_1_heap") {
+            None | Some(JsonValue::Null) => None,
+            Some(v) => Some(HeapStatistics::try_from(v)?),
+        };
+
+        let part_2_heap = match json.get("part_2_heap") {
+            None | Some(JsonValue::Null) => None,
+            Some(v) => Some(HeapStatistics::try_from(v)?),
+        };
+
         Ok(Timing {
             day,
             part_1: part_1.cloned(),
             part_2: part_2.cloned(),
+            part_1_stats,
+            part_2_stats,
+            part_1_heap,
+            part_2_heap,
             total_nanos,
         })
     }
@@ -177,6 +364,96 @@ impl TryFrom<&JsonValue> for Timing {
 
 /* -------------------------------------------------------------------------- */
 
+impl From<&Statistics> for JsonValue {
+    fn from(value: &Statistics) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("mean".into(), JsonValue::Number(value.mean));
+        map.insert("min".into(), JsonValue::Number(value.min));
+        map.insert("median".into(), JsonValue::Number(value.median));
+        map.insert("p95".into(), JsonValue::Number(value.p95));
+        map.insert("max".into(), JsonValue::Number(value.max));
+        map.insert("std_dev".into(), JsonValue::Number(value.std_dev));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Statistics {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected statistics to be a JSON object.")?;
+
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .ok_or(format!("Expected statistics.{key} to be a number."))
+        };
+
+        Ok(Statistics {
+            mean: number("mean")?,
+            min: number("min")?,
+            median: number("median")?,
+            p95: number("p95")?,
+            max: number("max")?,
+            std_dev: number("std_dev")?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&HeapStatistics> for JsonValue {
+    #[allow(clippy::cast_precision_loss)]
+    fn from(value: &HeapStatistics) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "peak_bytes".into(),
+            JsonValue::Number(value.peak_bytes as f64),
+        );
+        map.insert(
+            "total_allocations".into(),
+            JsonValue::Number(value.total_allocations as f64),
+        );
+        map.insert(
+            "total_bytes".into(),
+            JsonValue::Number(value.total_bytes as f64),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for HeapStatistics {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected heap statistics to be a JSON object.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>())
+                .map(|x| *x as u64)
+                .ok_or(format!("Expected heap_statistics.{key} to be a number."))
+        };
+
+        Ok(HeapStatistics {
+            peak_bytes: number("peak_bytes")?,
+            total_allocations: number("total_allocations")?,
+            total_bytes: number("total_bytes")?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
 #[cfg(feature = "test_lib")]
 mod tests {
     use crate::day;
@@ -190,18 +467,30 @@ mod tests {
                     day: day!(1),
                     part_1: Some("10ms".into()),
                     part_2: Some("20ms".into()),
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    part_1_heap: None,
+                    part_2_heap: None,
                     total_nanos: 3e+10,
                 },
                 Timing {
                     day: day!(2),
                     part_1: Some("30ms".into()),
                     part_2: Some("40ms".into()),
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    part_1_heap: None,
+                    part_2_heap: None,
                     total_nanos: 7e+10,
                 },
                 Timing {
                     day: day!(4),
                     part_1: Some("40ms".into()),
                     part_2: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    part_1_heap: None,
+                    part_2_heap: None,
                     total_nanos: 4e+10,
                 },
             ],
@@ -281,11 +570,15 @@ mod tests {
                     day: day!(1),
                     part_1: Some("1ms".into()),
                     part_2: Some("2ms".into()),
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    part_1_heap: None,
+                    part_2_heap: None,
                     total_nanos: 3_000_000_000_f64,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), true);
+            assert_eq!(timings.is_day_complete(day!(1)), true);
         }
 
         #[test]
@@ -295,11 +588,15 @@ mod tests {
                     day: day!(1),
                     part_1: Some("1ms".into()),
                     part_2: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    part_1_heap: None,
+                    part_2_heap: None,
                     total_nanos: 1_000_000_000_f64,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
 
         #[test]
@@ -309,18 +606,91 @@ mod tests {
                     day: day!(1),
                     part_1: None,
                     part_2: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    part_1_heap: None,
+                    part_2_heap: None,
                     total_nanos: 0.0,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
+        }
+    }
+
+    mod statistics {
+        use std::time::Duration;
+
+        use crate::template::timings::{Statistics, Timings};
+
+        #[test]
+        fn computes_statistics() {
+            let samples: Vec<Duration> = (1..=20).rev().map(Duration::from_nanos).collect();
+            let stats = Statistics::from_samples(&samples);
+            assert_eq!(stats.mean, 10.5);
+            assert_eq!(stats.min, 1.0);
+            assert_eq!(stats.median, 10.5);
+            assert_eq!(stats.p95, 19.0);
+            assert_eq!(stats.max, 20.0);
+            assert!((stats.std_dev - 5.766_281).abs() < 1e-6);
+        }
+
+        #[test]
+        fn handles_single_sample() {
+            let stats = Statistics::from_samples(&[Duration::from_nanos(42)]);
+            assert_eq!(stats.median, 42.0);
+            assert_eq!(stats.p95, 42.0);
+            assert_eq!(stats.std_dev, 0.0);
+        }
+
+        #[test]
+        fn roundtrips_json() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": null, "part_1_stats": { "mean": 1000000, "min": 900000, "median": 950000, "p95": 1200000, "max": 1500000, "std_dev": 20000 }, "total_nanos": 1000000 }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            let stats = timing.part_1_stats.clone().unwrap();
+            assert_eq!(stats.median, 950_000_f64);
+            assert_eq!(timing.part_2_stats, None);
+
+            let value = tinyjson::JsonValue::from(timings.clone());
+            let reparsed = Timings::try_from(value.stringify().unwrap()).unwrap();
+            assert_eq!(reparsed.data[0].part_1_stats, Some(stats));
+        }
+    }
+
+    mod heap_statistics {
+        use crate::template::timings::{HeapStatistics, Timings, format_bytes};
+
+        #[test]
+        fn formats_bytes() {
+            assert_eq!(format_bytes(0), "0 B");
+            assert_eq!(format_bytes(1023), "1023 B");
+            assert_eq!(format_bytes(1536), "1.5 KiB");
+            assert_eq!(format_bytes(3 * 1024 * 1024), "3.0 MiB");
+        }
+
+        #[test]
+        fn roundtrips_json() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": "2ms", "part_1_heap": { "peak_bytes": 232, "total_allocations": 3, "total_bytes": 276 }, "part_2_heap": null, "total_nanos": 3000000 }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let heap = HeapStatistics {
+                peak_bytes: 232,
+                total_allocations: 3,
+                total_bytes: 276,
+            };
+            assert_eq!(timings.data[0].part_1_heap, Some(heap.clone()));
+            assert_eq!(timings.data[0].part_2_heap, None);
+
+            let value = tinyjson::JsonValue::from(timings.clone());
+            let reparsed = Timings::try_from(value.stringify().unwrap()).unwrap();
+            assert_eq!(reparsed.data[0].part_1_heap, Some(heap));
         }
     }
 
     mod merge {
         use crate::{
             day,
-            template::timings::{Timing, Timings},
+            template::timings::{HeapStatistics, Timing, Timings},
         };
 
         use super::get_mock_timings;
@@ -333,6 +703,10 @@ mod tests {
                     day: day!(3),
                     part_1: None,
                     part_2: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    part_1_heap: None,
+                    part_2_heap: None,
                     total_nanos: 0_f64,
                 }],
             };
@@ -353,6 +727,10 @@ mod tests {
                     day: day!(2),
                     part_1: None,
                     part_2: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    part_1_heap: None,
+                    part_2_heap: None,
                     total_nanos: 0_f64,
                 }],
             };
@@ -365,6 +743,57 @@ mod tests {
             assert_eq!(merged.data[2].day, day!(4));
         }
 
+        #[test]
+        fn keeps_heap_statistics() {
+            let heap = HeapStatistics {
+                peak_bytes: 1024,
+                total_allocations: 8,
+                total_bytes: 4096,
+            };
+
+            let mut timings = get_mock_timings();
+            timings.data[1].part_1_heap = Some(heap.clone());
+
+            let mut other = get_mock_timings();
+            other.data[1].total_nanos = 0_f64;
+            let merged = timings.merge(&other);
+            assert_eq!(merged.data[1].total_nanos, 0_f64);
+            assert_eq!(merged.data[1].part_1_heap, Some(heap));
+            assert_eq!(merged.data[1].part_2_heap, None);
+
+            let newer = HeapStatistics {
+                peak_bytes: 512,
+                ..merged.data[1].part_1_heap.clone().unwrap()
+            };
+            other.data[1].part_1_heap = Some(newer.clone());
+            let merged = merged.merge(&other);
+            assert_eq!(merged.data[1].part_1_heap, Some(newer));
+        }
+
+        #[test]
+        fn merges_only_heap_statistics() {
+            let heap = HeapStatistics {
+                peak_bytes: 1024,
+                total_allocations: 8,
+                total_bytes: 4096,
+            };
+
+            let timings = get_mock_timings();
+            let mut other = get_mock_timings();
+            other.data[1].total_nanos = 0_f64;
+            other.data[1].part_2_heap = Some(heap.clone());
+            other.data.push(Timing {
+                day: day!(5),
+                ..other.data[1].clone()
+            });
+
+            let merged = timings.merge_heap(&other);
+            assert_eq!(merged.data.len(), 3);
+            assert_eq!(merged.data[1].total_nanos, timings.data[1].total_nanos);
+            assert_eq!(merged.data[1].part_1, timings.data[1].part_1);
+            assert_eq!(merged.data[1].part_2_heap, Some(heap));
+        }
+
         #[test]
         fn handles_empty_timings() {
             let timings = Timings::default();